@@ -0,0 +1,295 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use engine::{Bitboard, Board, CellContents, Direction};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+/// `Board::press` as it was before lanes were compacted in a single pass, copied over with only the bookkeeping it
+/// needs from `Board`: push every tile one step at a time until nothing moves, merge each lane, then push again.
+/// It's kept here only as a baseline to measure against.
+mod legacy {
+    use std::iter::Rev;
+    use std::slice::Iter;
+
+    use engine::{Board, Cell, CellContents, Direction, Merge, MoveOutcome, Slide};
+    use itertools::{Either, Itertools};
+
+    pub struct LegacyBoard {
+        pub cells: Vec<Cell>,
+        width: usize,
+        height: usize,
+        score: usize,
+        best_score: usize,
+        move_count: usize,
+        goal_tile: usize,
+        reached_goal: bool,
+    }
+
+    impl LegacyBoard {
+        pub fn from_board(board: &Board) -> Self {
+            Self {
+                cells: board.cells.clone(),
+                width: board.width(),
+                height: board.height(),
+                score: board.score(),
+                best_score: board.best_score(),
+                move_count: board.move_count(),
+                goal_tile: board.goal_tile(),
+                reached_goal: board.reached_goal(),
+            }
+        }
+
+        fn move_cell_into_cell(&mut self, source_cell_idx: usize, dest_cell_idx: usize, origins: &mut [Option<usize>]) {
+            self.cells[dest_cell_idx].contents = self.cells[source_cell_idx].contents;
+            // And empty the source cell, since it's been moved
+            self.cells[source_cell_idx].contents = CellContents::Empty;
+            // The tile carries its starting position along with it
+            origins[dest_cell_idx] = origins[source_cell_idx].take();
+        }
+
+        fn cell_indexes_by_row(&self) -> Vec<Vec<usize>> {
+            (0..self.height).map(|row_idx| {
+                (0..self.width).map(|col_idx| {
+                    col_idx + (row_idx * self.width)
+                }).collect::<Vec<_>>()
+            }).collect::<Vec<_>>()
+        }
+
+        fn cell_indexes_by_col(&self) -> Vec<Vec<usize>> {
+            (0..self.width).map(|col_idx| {
+                (0..self.height).map(|row_idx| {
+                    col_idx + (row_idx * self.width)
+                }).collect::<Vec<_>>()
+            }).collect::<Vec<_>>()
+        }
+
+        fn iter_axis_in_direction<'a>(
+            direction: Direction,
+            cell_indexes_by_col: &'a [Vec<usize>],
+            cell_indexes_by_row: &'a [Vec<usize>],
+        ) -> Either<Iter<'a, Vec<usize>>, Rev<Iter<'a, Vec<usize>>>> {
+            match direction {
+                Direction::Left => Either::Left(cell_indexes_by_col.iter()),
+                Direction::Right => Either::Right(cell_indexes_by_col.iter().rev()),
+                Direction::Up => Either::Left(cell_indexes_by_row.iter()),
+                Direction::Down => Either::Right(cell_indexes_by_row.iter().rev()),
+            }
+        }
+
+        fn push_cells_to_close_empty_gaps(&mut self, direction: Direction, origins: &mut [Option<usize>]) {
+            let cell_indexes_by_col = self.cell_indexes_by_col();
+            let cell_indexes_by_row = self.cell_indexes_by_row();
+            loop {
+                let mut did_modify_cells = false;
+                let row_iter = Self::iter_axis_in_direction(direction, &cell_indexes_by_col, &cell_indexes_by_row);
+                for (dest_row, source_row) in row_iter.tuple_windows::<(&Vec<usize>, &Vec<usize>)>() {
+                    for (dest_cell_idx, source_cell_idx) in dest_row.iter().zip(source_row.iter()) {
+                        let dest_cell = &self.cells[*dest_cell_idx];
+                        let source_cell = &self.cells[*source_cell_idx];
+                        if source_cell.is_empty() {
+                            // If the source cell is empty, we have nothing to do
+                            continue;
+                        }
+                        if dest_cell.is_empty() {
+                            // If the destination cell is empty, copy the source cell
+                            self.move_cell_into_cell(*source_cell_idx, *dest_cell_idx, origins);
+                            did_modify_cells = true;
+                            break;
+                        }
+                    }
+                }
+                if !did_modify_cells {
+                    break;
+                }
+            }
+        }
+
+        fn lanes_in_direction(&self, direction: Direction) -> Vec<Vec<usize>> {
+            // Each lane lists its cell indexes starting from the edge that tiles are being pushed towards
+            let (lanes, towards_start) = match direction {
+                Direction::Left => (self.cell_indexes_by_row(), true),
+                Direction::Right => (self.cell_indexes_by_row(), false),
+                Direction::Up => (self.cell_indexes_by_col(), true),
+                Direction::Down => (self.cell_indexes_by_col(), false),
+            };
+            if towards_start {
+                return lanes;
+            }
+            lanes.into_iter().map(|lane| lane.into_iter().rev().collect()).collect()
+        }
+
+        fn merge_contiguous_cells_in_lane(
+            &mut self,
+            lane: &[usize],
+            origins: &mut [Option<usize>],
+            pending_merges: &mut Vec<(usize, usize, usize)>,
+        ) {
+            let mut cursor = 0;
+            while cursor + 1 < lane.len() {
+                let dest_cell_idx = lane[cursor];
+                let source_cell_idx = lane[cursor + 1];
+                match (self.cells[dest_cell_idx].contents, self.cells[source_cell_idx].contents) {
+                    (CellContents::Occupied(dest_value), CellContents::Occupied(source_value))
+                        if dest_value == source_value =>
+                    {
+                        // Combine into the destination cell
+                        self.cells[dest_cell_idx].contents = CellContents::Occupied(dest_value * 2);
+                        // Clear the contents of the source cell, because it's been merged
+                        self.cells[source_cell_idx].contents = CellContents::Empty;
+                        let source_origin = origins[source_cell_idx].take().unwrap();
+                        pending_merges.push((origins[dest_cell_idx].unwrap(), source_origin, dest_value * 2));
+                        cursor += 2;
+                    }
+                    // Either one of the cells is empty, or they don't contain the same value
+                    _ => cursor += 1,
+                }
+            }
+        }
+
+        fn merge_contiguous_cells_in_direction(
+            &mut self,
+            direction: Direction,
+            origins: &mut [Option<usize>],
+        ) -> Vec<(usize, usize, usize)> {
+            let mut pending_merges = vec![];
+            for lane in self.lanes_in_direction(direction).iter() {
+                self.merge_contiguous_cells_in_lane(lane, origins, &mut pending_merges);
+            }
+            pending_merges
+        }
+
+        pub fn press(&mut self, direction: Direction) -> MoveOutcome {
+            // Keep track of where each tile started out, so we can describe what happened afterwards
+            let mut origins = self.cells.iter().enumerate().map(|(idx, cell)| {
+                (!cell.is_empty()).then_some(idx)
+            }).collect::<Vec<_>>();
+
+            self.push_cells_to_close_empty_gaps(direction, &mut origins);
+            let pending_merges = self.merge_contiguous_cells_in_direction(direction, &mut origins);
+            self.push_cells_to_close_empty_gaps(direction, &mut origins);
+
+            let mut outcome = self.describe_move(direction, &origins, &pending_merges);
+            if !self.reached_goal && outcome.merges.iter().any(|merge| merge.value >= self.goal_tile) {
+                self.reached_goal = true;
+                outcome.reached_goal = true;
+            }
+            if outcome.board_changed() {
+                // Presses that don't move anything don't count as a turn
+                self.move_count += 1;
+            }
+            self.score += outcome.score_delta;
+            self.best_score = self.best_score.max(self.score);
+            outcome
+        }
+
+        fn describe_move(
+            &self,
+            direction: Direction,
+            origins: &[Option<usize>],
+            pending_merges: &[(usize, usize, usize)],
+        ) -> MoveOutcome {
+            let mut outcome = MoveOutcome {
+                direction,
+                merges: vec![],
+                slides: vec![],
+                score_delta: 0,
+                reached_goal: false,
+            };
+            for (dest_cell_idx, origin) in origins.iter().enumerate() {
+                let Some(origin) = *origin else {
+                    continue;
+                };
+                let destination = self.cells[dest_cell_idx].coords;
+                let merge = pending_merges.iter().find(|(dest_origin, _, _)| *dest_origin == origin);
+                if let Some((_, source_origin, value)) = merge {
+                    outcome.merges.push(Merge {
+                        sources: (self.cells[origin].coords, self.cells[*source_origin].coords),
+                        destination,
+                        value: *value,
+                    });
+                    outcome.score_delta += value;
+                } else if origin != dest_cell_idx {
+                    outcome.slides.push(Slide {
+                        from: self.cells[origin].coords,
+                        to: destination,
+                    });
+                }
+            }
+            outcome
+        }
+    }
+}
+
+/// A spread of mid-game positions, so that every benchmark sees the same mix of slides and merges
+fn sample_boards() -> Vec<Board> {
+    let mut rng = ChaCha8Rng::seed_from_u64(2048);
+    (0..64).map(|_| {
+        let mut board = Board::with_seed(rng.gen());
+        for cell in board.cells.iter_mut() {
+            if rng.gen_bool(0.6) {
+                cell.contents = CellContents::Occupied(1 << rng.gen_range(1..=5));
+            }
+        }
+        board
+    }).collect()
+}
+
+fn bench_press(c: &mut Criterion) {
+    let boards = sample_boards();
+    // The baseline only means something if it still makes the same moves as the real thing
+    for board in boards.iter() {
+        for direction in Direction::ALL {
+            let (mut current, mut legacy) = (board.clone(), legacy::LegacyBoard::from_board(board));
+            // The slides and merges are listed in a different order, but should add up to the same move
+            let (expected, actual) = (current.press(direction), legacy.press(direction));
+            assert_eq!(actual.score_delta, expected.score_delta);
+            assert_eq!((actual.slides.len(), actual.merges.len()), (expected.slides.len(), expected.merges.len()));
+            assert_eq!(legacy.cells, current.cells);
+        }
+    }
+    let mut group = c.benchmark_group("press");
+
+    group.bench_function("legacy", |b| {
+        b.iter_batched(
+            || boards.iter().map(legacy::LegacyBoard::from_board).collect::<Vec<_>>(),
+            |mut boards| {
+                for board in boards.iter_mut() {
+                    for direction in Direction::ALL {
+                        black_box(board.press(direction));
+                    }
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("single_pass", |b| {
+        b.iter_batched(
+            || boards.clone(),
+            |mut boards| {
+                for board in boards.iter_mut() {
+                    for direction in Direction::ALL {
+                        black_box(board.press(direction));
+                    }
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("bitboard", |b| {
+        let bitboards = boards.iter().map(|board| Bitboard::try_from(board).unwrap()).collect::<Vec<_>>();
+        b.iter(|| {
+            for bitboard in bitboards.iter() {
+                let mut bitboard = *bitboard;
+                for direction in Direction::ALL {
+                    bitboard = black_box(bitboard.press(direction)).0;
+                }
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_press);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+//! Simple players that don't look ahead at all. They're no match for the searching players, but they make useful
+//! yardsticks, and they're quick enough to play thousands of games.
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Board, Direction, Strategy};
+
+/// The first direction in `preferences` that changes the board
+fn first_legal(board: &Board, preferences: &[Direction]) -> Option<Direction> {
+    let legal_moves = board.legal_moves();
+    preferences.iter().copied().find(|direction| legal_moves.contains(direction))
+}
+
+/// Picks any legal move, all equally likely
+#[derive(Debug, Clone)]
+pub struct RandomPlayer {
+    rng: ChaCha8Rng,
+}
+
+impl Default for RandomPlayer {
+    fn default() -> Self {
+        Self::with_seed(thread_rng().gen())
+    }
+}
+
+impl RandomPlayer {
+    /// Two players starting from the same seed pick the same moves on the same boards
+    pub fn with_seed(seed: u64) -> Self {
+        Self { rng: ChaCha8Rng::seed_from_u64(seed) }
+    }
+}
+
+impl Strategy for RandomPlayer {
+    fn choose(&mut self, board: &Board) -> Direction {
+        board.legal_moves().choose(&mut self.rng).copied().unwrap_or(Direction::Left)
+    }
+}
+
+/// Keeps the big tiles packed into the bottom-left corner, by pressing down or left whenever it can and only
+/// pressing right (or, as a last resort, up) when it has to
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CornerPlayer;
+
+impl CornerPlayer {
+    const PREFERENCES: [Direction; 4] = [Direction::Down, Direction::Left, Direction::Right, Direction::Up];
+}
+
+impl Strategy for CornerPlayer {
+    fn choose(&mut self, board: &Board) -> Direction {
+        first_legal(board, &Self::PREFERENCES).unwrap_or(Direction::Down)
+    }
+}
+
+/// Takes whichever move scores the most points right now. Ties go to the direction that comes first in
+/// `Direction::ALL`.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct GreedyPlayer;
+
+impl Strategy for GreedyPlayer {
+    fn choose(&mut self, board: &Board) -> Direction {
+        let mut best: Option<(Direction, usize)> = None;
+        for direction in Direction::ALL {
+            let outcome = board.clone().press(direction);
+            if !outcome.board_changed() {
+                continue;
+            }
+            if best.is_none_or(|(_, best_score)| outcome.score_delta > best_score) {
+                best = Some((direction, outcome.score_delta));
+            }
+        }
+        best.map_or(Direction::Left, |(direction, _)| direction)
+    }
+}
+
+/// Presses each direction in turn (left, down, right, up), skipping any that wouldn't move anything
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CyclicPlayer {
+    next: usize,
+}
+
+impl CyclicPlayer {
+    const CYCLE: [Direction; 4] = [Direction::Left, Direction::Down, Direction::Right, Direction::Up];
+}
+
+impl Strategy for CyclicPlayer {
+    fn choose(&mut self, board: &Board) -> Direction {
+        // Start from wherever the cycle left off
+        let mut order = Self::CYCLE;
+        order.rotate_left(self.next);
+        let direction = first_legal(board, &order).unwrap_or(order[0]);
+        self.next = (Self::CYCLE.iter().position(|d| *d == direction).unwrap() + 1) % Self::CYCLE.len();
+        direction
+    }
+}
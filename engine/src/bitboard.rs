@@ -0,0 +1,192 @@
+use lazy_static::lazy_static;
+
+use crate::{Board, BoardCoordinate, CellContents, Direction, Rules};
+
+/// The only board size that fits in a bitboard
+pub const BITBOARD_SIZE: usize = 4;
+/// Each cell holds a 4-bit exponent, so 2^15 is the largest tile we can represent
+pub const MAX_BITBOARD_EXPONENT: u8 = 15;
+
+const ROW_MASK: u64 = 0xffff;
+const CELL_MASK: u64 = 0xf;
+
+/// The result of pushing every possible row to the left, indexed by the packed row
+struct RowTables {
+    left: Vec<u16>,
+    right: Vec<u16>,
+    /// Points earned by pushing the row, which is the same whichever way it's pushed
+    score: Vec<u32>,
+}
+
+lazy_static! {
+    static ref ROW_TABLES: RowTables = RowTables::new();
+}
+
+fn reverse_row(row: u16) -> u16 {
+    (row >> 12) | ((row >> 4) & 0x00f0) | ((row << 4) & 0x0f00) | (row << 12)
+}
+
+impl RowTables {
+    fn new() -> Self {
+        let mut left = vec![0; 1 << 16];
+        let mut right = vec![0; 1 << 16];
+        let mut score = vec![0; 1 << 16];
+        for row in 0..=u16::MAX {
+            let (pushed, row_score) = Self::push_row_left(row);
+            left[row as usize] = pushed;
+            score[row as usize] = row_score;
+            // Pushing right is the same as pushing the mirrored row left, then mirroring the result back
+            right[reverse_row(row) as usize] = reverse_row(pushed);
+        }
+        Self { left, right, score }
+    }
+
+    /// Applies the classic rule to one row: close the gaps, then merge each pair at most once, starting from the
+    /// left edge
+    fn push_row_left(row: u16) -> (u16, u32) {
+        let exponents = (0..BITBOARD_SIZE).map(|i| ((row >> (i * 4)) & 0xf) as u8).filter(|exponent| *exponent != 0);
+        let mut out = vec![];
+        let mut score = 0;
+        let mut exponents = exponents.peekable();
+        while let Some(exponent) = exponents.next() {
+            // Two of the largest tiles have nowhere to go, so leave them be rather than overflowing the cell
+            if exponent < MAX_BITBOARD_EXPONENT && exponents.peek() == Some(&exponent) {
+                exponents.next();
+                out.push(exponent + 1);
+                score += 1 << (exponent + 1);
+            }
+            else {
+                out.push(exponent);
+            }
+        }
+        let pushed = out.iter().enumerate().fold(0, |acc, (i, exponent)| acc | ((*exponent as u16) << (i * 4)));
+        (pushed, score)
+    }
+}
+
+/// A 4x4 board packed into a single integer, for searches that need to look at a huge number of positions.
+/// Each cell is a nibble holding the exponent of its tile (or 0 if the cell is empty), stored row by row starting
+/// from the least significant nibble at the top-left.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    fn shift_for(coords: BoardCoordinate) -> u32 {
+        let BoardCoordinate(col_idx, row_idx) = coords;
+        assert!(col_idx < BITBOARD_SIZE && row_idx < BITBOARD_SIZE, "{coords} is outside the board");
+        ((col_idx + (row_idx * BITBOARD_SIZE)) * 4) as u32
+    }
+
+    /// The exponent of the tile at the given coordinates, or 0 if the cell is empty
+    pub fn exponent_at(&self, coords: BoardCoordinate) -> u8 {
+        ((self.0 >> Self::shift_for(coords)) & CELL_MASK) as u8
+    }
+
+    pub fn with_exponent_at(self, coords: BoardCoordinate, exponent: u8) -> Self {
+        assert!(exponent <= MAX_BITBOARD_EXPONENT, "2^{exponent} doesn't fit in a bitboard cell");
+        let shift = Self::shift_for(coords);
+        Self((self.0 & !(CELL_MASK << shift)) | ((exponent as u64) << shift))
+    }
+
+    fn row(&self, row_idx: usize) -> u16 {
+        ((self.0 >> (row_idx * 16)) & ROW_MASK) as u16
+    }
+
+    /// Flips the board along its main diagonal, so that columns can be handled as rows
+    pub fn transpose(self) -> Self {
+        let x = self.0;
+        let a1 = x & 0xf0f0_0f0f_f0f0_0f0f;
+        let a2 = x & 0x0000_f0f0_0000_f0f0;
+        let a3 = x & 0x0f0f_0000_0f0f_0000;
+        let a = a1 | (a2 << 12) | (a3 >> 12);
+        let b1 = a & 0xff00_ff00_00ff_00ff;
+        let b2 = a & 0x00ff_00ff_0000_0000;
+        let b3 = a & 0x0000_0000_ff00_ff00;
+        Self(b1 | (b2 >> 24) | (b3 << 24))
+    }
+
+    fn push_rows(self, table: &[u16]) -> (Self, usize) {
+        let tables = &*ROW_TABLES;
+        let mut pushed = 0;
+        let mut score = 0;
+        for row_idx in 0..BITBOARD_SIZE {
+            let row = self.row(row_idx);
+            pushed |= (table[row as usize] as u64) << (row_idx * 16);
+            score += tables.score[row as usize] as usize;
+        }
+        (Self(pushed), score)
+    }
+
+    /// Returns the board after the move, along with the points it earned. Unlike `Board::press`, this doesn't
+    /// describe what happened to each tile: compare the result against `self` to find out whether anything moved.
+    pub fn press(self, direction: Direction) -> (Self, usize) {
+        let tables = &*ROW_TABLES;
+        match direction {
+            Direction::Left => self.push_rows(&tables.left),
+            Direction::Right => self.push_rows(&tables.right),
+            // Columns are handled by pushing the rows of the transposed board
+            Direction::Up => {
+                let (pushed, score) = self.transpose().push_rows(&tables.left);
+                (pushed.transpose(), score)
+            }
+            Direction::Down => {
+                let (pushed, score) = self.transpose().push_rows(&tables.right);
+                (pushed.transpose(), score)
+            }
+        }
+    }
+
+    pub fn legal_moves(self) -> Vec<Direction> {
+        Direction::ALL.into_iter().filter(|direction| self.press(*direction).0 != self).collect()
+    }
+
+    pub fn empty_cell_count(&self) -> usize {
+        (0..BITBOARD_SIZE * BITBOARD_SIZE).filter(|i| (self.0 >> (i * 4)) & CELL_MASK == 0).count()
+    }
+
+    pub fn max_exponent(&self) -> u8 {
+        (0..BITBOARD_SIZE * BITBOARD_SIZE).map(|i| ((self.0 >> (i * 4)) & CELL_MASK) as u8).max().unwrap()
+    }
+}
+
+impl TryFrom<&Board> for Bitboard {
+    type Error = ();
+
+    /// Only 4x4 boards whose tiles are all powers of two (up to 2^15) can be packed
+    fn try_from(board: &Board) -> Result<Self, Self::Error> {
+        if board.width() != BITBOARD_SIZE || board.height() != BITBOARD_SIZE {
+            return Err(());
+        }
+        let mut bitboard = Self::default();
+        for cell in board.cells.iter() {
+            let exponent = match cell.contents {
+                CellContents::Empty => 0,
+                CellContents::Occupied(value) if value >= 2 && value.is_power_of_two() => value.trailing_zeros(),
+                // Not a tile that can appear in the game
+                CellContents::Occupied(_) => return Err(()),
+            };
+            if exponent > MAX_BITBOARD_EXPONENT as u32 {
+                return Err(());
+            }
+            bitboard = bitboard.with_exponent_at(cell.coords, exponent as u8);
+        }
+        Ok(bitboard)
+    }
+}
+
+impl From<Bitboard> for Board {
+    /// Produces a board in a fresh game under the classic rules, with the same tiles as the bitboard. The game is
+    /// seeded with 0, so that converting the same bitboard always gives the same board.
+    fn from(bitboard: Bitboard) -> Self {
+        let mut board = Board::with_seed_and_rules(0, Rules::classic());
+        for cell_idx in 0..board.cells.len() {
+            let coords = board.cells[cell_idx].coords;
+            let contents = match bitboard.exponent_at(coords) {
+                0 => CellContents::Empty,
+                exponent => CellContents::Occupied(1 << exponent),
+            };
+            board.set_contents_at(coords, contents);
+        }
+        board
+    }
+}
@@ -0,0 +1,515 @@
+use std::fmt::{Display, Formatter};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use crate::save::SavedGame;
+use crate::{Direction, Merge, MoveOutcome, Rules, Slide, Tile, TileEvent, TileId};
+
+/// The width and height of the board in the original game
+pub const DEFAULT_BOARD_SIZE: usize = 4;
+/// The tile the player needs to create to win, as in the original game
+pub const DEFAULT_GOAL_TILE: usize = 2048;
+/// Every game starts out with this many tiles on the board
+pub const STARTING_TILE_COUNT: usize = 2;
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+pub struct BoardCoordinate(pub usize, pub usize);
+
+impl Display for BoardCoordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum CellContents {
+    Empty,
+    Occupied(usize),
+}
+
+impl CellContents {
+
+    fn as_padded_str(&self) -> String {
+        match &self {
+            Self::Empty => "    ".to_string(),
+            Self::Occupied(value) => format!("{: ^4}", value),
+        }
+    }
+
+
+    pub fn unwrap(&self) -> usize {
+        match self {
+            Self::Empty => panic!("Expected a non-empty cell"),
+            Self::Occupied(val) => *val
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cell {
+    pub coords: BoardCoordinate,
+    pub contents: CellContents,
+}
+
+impl Cell {
+    fn with_coords(coords: BoardCoordinate) -> Self {
+        Self {
+            coords,
+            contents: CellContents::Empty,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self.contents, CellContents::Empty)
+    }
+
+}
+
+/// Saved and loaded through `SavedGame`, which checks that a loaded board is one the game could actually produce
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "SavedGame", try_from = "SavedGame")]
+pub struct Board {
+    /// Stored row by row, starting from the top-left. The dimensions of the board are given by its `rules`.
+    /// Prefer `set_contents_at` to editing these directly, so that each tile gets an id straight away.
+    pub cells: Vec<Cell>,
+    /// The id of the tile in each cell, in the same order as `cells`
+    tile_ids: Vec<Option<TileId>>,
+    next_tile_id: u64,
+    /// What's happened to the tiles since the start of the last press, for renderers to animate
+    tile_events: Vec<TileEvent>,
+    /// The sum of the values of every tile merged in the current game
+    pub(crate) score: usize,
+    /// The highest score seen so far. This outlives individual games, so it isn't cleared by `empty()`.
+    pub(crate) best_score: usize,
+    /// The number of presses in the current game that actually changed the board
+    pub(crate) move_count: usize,
+    pub(crate) rules: Rules,
+    /// Whether the goal tile has been created in the current game. The player may keep going afterwards, but we
+    /// only want to announce the win once.
+    pub(crate) reached_goal: bool,
+    /// The seed that `rng` started from. Replaying the same inputs on a board with the same seed reproduces the
+    /// same game, on any platform.
+    pub(crate) seed: u64,
+    /// Decides where new tiles appear, and what they're worth
+    pub(crate) rng: ChaCha8Rng,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Self::with_rules(Rules::default())
+    }
+
+    pub fn with_rules(rules: Rules) -> Self {
+        // Pick a fresh seed, but hold on to it so the game can still be reproduced later
+        Self::with_seed_and_rules(thread_rng().gen(), rules)
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_seed_and_rules(seed, Rules::default())
+    }
+
+    /// Draws the seed from the provided generator
+    pub fn with_rng<R: Rng>(rng: &mut R) -> Self {
+        Self::with_seed(rng.gen())
+    }
+
+    pub fn with_seed_and_rules(seed: u64, rules: Rules) -> Self {
+        let cells = Self::empty_cells(&rules);
+        Self {
+            tile_ids: vec![None; cells.len()],
+            next_tile_id: 0,
+            tile_events: vec![],
+            cells,
+            score: 0,
+            best_score: 0,
+            move_count: 0,
+            rules,
+            reached_goal: false,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    fn empty_cells(rules: &Rules) -> Vec<Cell> {
+        assert!(rules.width > 0 && rules.height > 0, "A board needs at least one cell");
+        let mut cells = vec![];
+        for row_idx in 0..rules.height {
+            for col_idx in 0..rules.width {
+                cells.push(Cell::with_coords(BoardCoordinate(col_idx, row_idx)));
+            }
+        }
+        cells
+    }
+
+    pub fn width(&self) -> usize {
+        self.rules.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rules.height
+    }
+
+    fn cell_index(&self, coords: BoardCoordinate) -> usize {
+        let BoardCoordinate(col_idx, row_idx) = coords;
+        assert!(col_idx < self.width() && row_idx < self.height(), "{coords} is outside the board");
+        col_idx + (row_idx * self.width())
+    }
+
+    pub fn contents_at(&self, coords: BoardCoordinate) -> CellContents {
+        self.cells[self.cell_index(coords)].contents
+    }
+
+    /// Places a tile (with a fresh id) or clears the cell. Unlike a spawn, this isn't reported as a tile event.
+    pub fn set_contents_at(&mut self, coords: BoardCoordinate, contents: CellContents) {
+        let cell_idx = self.cell_index(coords);
+        self.cells[cell_idx].contents = contents;
+        self.tile_ids[cell_idx] = match contents {
+            CellContents::Empty => None,
+            CellContents::Occupied(_) => Some(self.new_tile_id()),
+        };
+    }
+
+    fn new_tile_id(&mut self) -> TileId {
+        let id = TileId(self.next_tile_id);
+        self.next_tile_id += 1;
+        id
+    }
+
+    /// Catches the ids up with any cells that were edited directly through `cells`
+    fn sync_tile_ids(&mut self) {
+        for cell_idx in 0..self.cells.len() {
+            match (self.cells[cell_idx].contents, self.tile_ids[cell_idx]) {
+                (CellContents::Empty, Some(_)) => self.tile_ids[cell_idx] = None,
+                (CellContents::Occupied(_), None) => self.tile_ids[cell_idx] = Some(self.new_tile_id()),
+                _ => {}
+            }
+        }
+    }
+
+    pub fn tile_id_at(&self, coords: BoardCoordinate) -> Option<TileId> {
+        self.tile_ids[self.cell_index(coords)]
+    }
+
+    /// Every tile on the board, row by row. Tiles placed by editing `cells` directly only show up once they've
+    /// been given an id, which happens at the next press or spawn.
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.cells.iter().zip(self.tile_ids.iter()).filter_map(|(cell, id)| match (cell.contents, id) {
+            (CellContents::Occupied(value), Some(id)) => Some(Tile { id: *id, coords: cell.coords, value }),
+            _ => None,
+        }).collect()
+    }
+
+    /// What's happened to the tiles since the last press began: the slides and merges of the press itself, then
+    /// any spawns that followed (and, if the game was restarted, the removal of the old tiles)
+    pub fn tile_events(&self) -> &[TileEvent] {
+        &self.tile_events
+    }
+
+    fn cell_indexes_by_row(&self) -> Vec<Vec<usize>> {
+        (0..self.height()).map(|row_idx| {
+            (0..self.width()).map(|col_idx| {
+                col_idx + (row_idx * self.width())
+            }).collect::<Vec<_>>()
+        }).collect::<Vec<_>>()
+    }
+
+    /// Returns where the new tile was placed, or `None` if the board had no free cells left
+    pub fn spawn_tile_in_random_location(&mut self) -> Option<BoardCoordinate> {
+        self.sync_tile_ids();
+        // Pick a random free cell
+        let free_cells = self.cells.iter_mut().filter(|elem|{
+            elem.is_empty()
+        });
+        let chosen_cell = free_cells.choose(&mut self.rng)?;
+        let spawn = *self.rules.spawn_table.choose_weighted(&mut self.rng, |spawn| spawn.weight).unwrap();
+        chosen_cell.contents = CellContents::Occupied(spawn.value);
+        let coords = chosen_cell.coords;
+
+        let id = self.new_tile_id();
+        let cell_idx = self.cell_index(coords);
+        self.tile_ids[cell_idx] = Some(id);
+        self.tile_events.push(TileEvent::Spawned { id, at: coords, value: spawn.value });
+        Some(coords)
+    }
+
+    /// Spawns however many tiles the rules call for after a move, stopping early if the board fills up
+    pub fn spawn_tiles_for_turn(&mut self) -> Vec<BoardCoordinate> {
+        (0..self.rules.tiles_per_turn).map_while(|_| self.spawn_tile_in_random_location()).collect()
+    }
+
+    /// The number of lanes that tiles travel along when pressing in this direction
+    fn lane_count(&self, direction: Direction) -> usize {
+        match direction {
+            Direction::Left | Direction::Right => self.height(),
+            Direction::Up | Direction::Down => self.width(),
+        }
+    }
+
+    fn lane_len(&self, direction: Direction) -> usize {
+        match direction {
+            Direction::Left | Direction::Right => self.width(),
+            Direction::Up | Direction::Down => self.height(),
+        }
+    }
+
+    /// The index of a cell within a lane, where position 0 is at the edge that tiles are being pushed towards
+    fn lane_cell_index(&self, direction: Direction, lane_idx: usize, position: usize) -> usize {
+        let (width, height) = (self.width(), self.height());
+        match direction {
+            Direction::Left => position + (lane_idx * width),
+            Direction::Right => (width - 1 - position) + (lane_idx * width),
+            Direction::Up => lane_idx + (position * width),
+            Direction::Down => lane_idx + ((height - 1 - position) * width),
+        }
+    }
+
+    fn record_slide(&self, outcome: &mut MoveOutcome, source_cell_idx: usize, dest_cell_idx: usize) {
+        if source_cell_idx != dest_cell_idx {
+            outcome.slides.push(Slide {
+                from: self.cells[source_cell_idx].coords,
+                to: self.cells[dest_cell_idx].coords,
+            });
+        }
+    }
+
+    fn press_lane(&mut self, direction: Direction, lane_idx: usize, outcome: &mut MoveOutcome) {
+        // Walk the lane once, starting from the edge that tiles are being pushed towards. Each tile either drops
+        // into the next free slot, or merges into the tile placed just before it.
+        // Each tile can take part in at most one merge per move. For example, pressing left on
+        // |  2 |  2 |  2 |  2 |
+        // gives
+        // |  4 |  4 |    |    |
+        // rather than a single 8. The user needs to do another turn to perform the next merge.
+        let mut next_free_position = 0;
+        // The last tile we placed, if it's still allowed to merge: (where it started out, where it ended up, value)
+        let mut mergeable_tile: Option<(usize, usize, usize)> = None;
+        for position in 0..self.lane_len(direction) {
+            let source_cell_idx = self.lane_cell_index(direction, lane_idx, position);
+            let CellContents::Occupied(value) = self.cells[source_cell_idx].contents else {
+                // Gaps are closed up just by skipping over them
+                continue;
+            };
+            // Pick the tile up. The spot it lands in is never further along the lane, so we can't clobber a tile
+            // we've yet to visit.
+            self.cells[source_cell_idx].contents = CellContents::Empty;
+            let id = self.tile_ids[source_cell_idx].take().unwrap();
+
+            match mergeable_tile.take() {
+                Some((dest_origin_idx, dest_cell_idx, dest_value)) if dest_value == value => {
+                    // Combine into the previously placed tile, which keeps its id
+                    self.cells[dest_cell_idx].contents = CellContents::Occupied(value * 2);
+                    self.tile_events.push(TileEvent::Merged {
+                        id,
+                        into: self.tile_ids[dest_cell_idx].unwrap(),
+                        from: self.cells[source_cell_idx].coords,
+                        to: self.cells[dest_cell_idx].coords,
+                        value: value * 2,
+                    });
+                    outcome.merges.push(Merge {
+                        sources: (self.cells[dest_origin_idx].coords, self.cells[source_cell_idx].coords),
+                        destination: self.cells[dest_cell_idx].coords,
+                        value: value * 2,
+                    });
+                    outcome.score_delta += value * 2;
+                }
+                previous_tile => {
+                    // The previously placed tile can no longer merge, so it's come to rest
+                    if let Some((previous_origin_idx, previous_cell_idx, _)) = previous_tile {
+                        self.record_slide(outcome, previous_origin_idx, previous_cell_idx);
+                    }
+                    let dest_cell_idx = self.lane_cell_index(direction, lane_idx, next_free_position);
+                    self.cells[dest_cell_idx].contents = CellContents::Occupied(value);
+                    self.tile_ids[dest_cell_idx] = Some(id);
+                    if dest_cell_idx != source_cell_idx {
+                        self.tile_events.push(TileEvent::Moved {
+                            id,
+                            from: self.cells[source_cell_idx].coords,
+                            to: self.cells[dest_cell_idx].coords,
+                        });
+                    }
+                    mergeable_tile = Some((source_cell_idx, dest_cell_idx, value));
+                    next_free_position += 1;
+                }
+            }
+        }
+        if let Some((previous_origin_idx, previous_cell_idx, _)) = mergeable_tile {
+            self.record_slide(outcome, previous_origin_idx, previous_cell_idx);
+        }
+    }
+
+    pub fn press(&mut self, direction: Direction) -> MoveOutcome {
+        self.sync_tile_ids();
+        self.tile_events.clear();
+        let mut outcome = MoveOutcome::new(direction);
+        for lane_idx in 0..self.lane_count(direction) {
+            self.press_lane(direction, lane_idx, &mut outcome);
+        }
+
+        if !self.reached_goal && outcome.merges.iter().any(|merge| merge.value >= self.rules.goal_tile) {
+            self.reached_goal = true;
+            outcome.reached_goal = true;
+        }
+        if outcome.board_changed() {
+            // Presses that don't move anything don't count as a turn
+            self.move_count += 1;
+        }
+        self.score += outcome.score_delta;
+        self.best_score = self.best_score.max(self.score);
+        outcome
+    }
+
+    pub fn is_full(&self) -> bool {
+        for cell in self.cells.iter() {
+            if cell.contents == CellContents::Empty {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The directions that would change the board if pressed
+    pub fn legal_moves(&self) -> Vec<Direction> {
+        Direction::ALL.into_iter().filter(|direction| {
+            // Try the move out on a scratch copy, so we don't disturb the real board
+            self.clone().press(*direction).board_changed()
+        }).collect()
+    }
+
+    /// Whether the player can still do anything. Note that a full board may still have moves left, if two
+    /// neighbouring tiles can be merged.
+    pub fn has_any_legal_move(&self) -> bool {
+        if !self.is_full() {
+            // There's always somewhere for a tile to slide to
+            return true;
+        }
+        !self.legal_moves().is_empty()
+    }
+
+    /// The value of the largest tile on the board, or 0 if the board is empty
+    pub fn max_tile(&self) -> usize {
+        self.cells.iter().map(|cell| match cell.contents {
+            CellContents::Empty => 0,
+            CellContents::Occupied(value) => value,
+        }).max().unwrap_or(0)
+    }
+
+    pub fn score(&self) -> usize {
+        self.score
+    }
+
+    pub fn best_score(&self) -> usize {
+        self.best_score
+    }
+
+    /// Taking back a move shouldn't take back the best score along with it
+    pub(crate) fn keep_best_score_from(&mut self, other: &Board) {
+        self.best_score = self.best_score.max(other.best_score);
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restarts the sequence of spawned tiles from the provided seed
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+
+    /// Draws a seed for the next game from this game's generator. Starting each game from its own seed means any one
+    /// of them can be replayed on its own, while a whole session still follows on from the seed it started with.
+    pub fn draw_next_seed(&mut self) -> u64 {
+        self.rng.gen()
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn goal_tile(&self) -> usize {
+        self.rules.goal_tile
+    }
+
+    pub fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    /// Whether the goal tile has been created at some point in the current game
+    pub fn reached_goal(&self) -> bool {
+        self.reached_goal
+    }
+
+    pub fn empty(&mut self) {
+        for (cell, id) in self.cells.iter_mut().zip(self.tile_ids.iter_mut()) {
+            if let (CellContents::Occupied(_), Some(id)) = (cell.contents, id.take()) {
+                self.tile_events.push(TileEvent::Removed { id, at: cell.coords });
+            }
+            cell.contents = CellContents::Empty
+        }
+        // Start the next game from zero, but hold on to the best score
+        self.score = 0;
+        self.move_count = 0;
+        self.reached_goal = false;
+    }
+
+    /// Clears the board and starts a new game from `seed`, with its starting tiles already spawned. If no seed is
+    /// given, one is drawn from the last game's generator. Returns the seed the new game started from.
+    pub fn start_new_game(&mut self, seed: Option<u64>) -> u64 {
+        let seed = seed.unwrap_or_else(|| self.draw_next_seed());
+        self.empty();
+        self.reseed(seed);
+        for _ in 0..STARTING_TILE_COUNT {
+            self.spawn_tile_in_random_location();
+        }
+        seed
+    }
+
+    /// Empties the board ready for a new game played under different rules (which might change its size)
+    pub fn reset_with_rules(&mut self, rules: Rules) {
+        self.empty();
+        self.cells = Self::empty_cells(&rules);
+        self.tile_ids = vec![None; self.cells.len()];
+        self.rules = rules;
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Let's just handle a maximum cell contents width of 4 characters, plus 3 spaces on either side for padding
+        // This will cause the presentation to go a bit wonky once the player hits the 16,384 tile, but I think
+        // they can handle it =)
+        let cell_width = 4 + 3 + 3;
+        let cell_width_including_inter_cell_border = cell_width + 1;
+
+        let horizontal_trim = "-".repeat(cell_width_including_inter_cell_border * self.width());
+        writeln!(f, "\nScore: {}    Best: {}", self.score, self.best_score)?;
+        writeln!(f, "{}-", horizontal_trim)?;
+
+        for cell_indexes_in_row in self.cell_indexes_by_row().iter() {
+            // Each tile should occupy a few lines vertically, to bulk out the presentation
+            for line_idx in 0..4 {
+                let empty_cell_line = format!("|{}", " ".repeat(cell_width));
+                match line_idx {
+                    1 => {
+                        for cell_idx in cell_indexes_in_row.iter() {
+                            let cell = &self.cells[*cell_idx];
+                            let cell_text = cell.contents.as_padded_str();
+                            write!(f, "|   {cell_text}   ")?;
+                        }
+                        writeln!(f, "|")?
+                    }
+                    3 => writeln!(f, "{}-", horizontal_trim)?,
+                    _ => writeln!(f, "{}|", empty_cell_line.repeat(self.width()))?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
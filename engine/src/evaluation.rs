@@ -0,0 +1,127 @@
+//! Scores positions for the players that search ahead. Each term measures one thing that tends to go with a
+//! healthy board, and the weights decide how they trade off against each other.
+//!
+//! Every term works on the exponents of the tiles (so a 1024 counts as 10) rather than their values, since it's
+//! the number of merges between two tiles that matters, not the gap between their values.
+use crate::{Board, BoardCoordinate, CellContents};
+
+/// The exponent of each cell (0 for an empty cell), grouped into rows and columns
+fn lines(board: &Board) -> Vec<Vec<u32>> {
+    let exponent_at = |col_idx, row_idx| match board.contents_at(BoardCoordinate(col_idx, row_idx)) {
+        CellContents::Empty => 0,
+        CellContents::Occupied(value) => value.ilog2(),
+    };
+    let rows = (0..board.height()).map(|row_idx| (0..board.width()).map(|col_idx| exponent_at(col_idx, row_idx)).collect());
+    let cols = (0..board.width()).map(|col_idx| (0..board.height()).map(|row_idx| exponent_at(col_idx, row_idx)).collect());
+    rows.chain(cols).collect()
+}
+
+/// How far the line is from rising or falling steadily along its length. Perfectly monotonic lines score 0.
+fn line_monotonicity(line: &[u32]) -> f64 {
+    let (mut rises, mut falls) = (0, 0);
+    for pair in line.windows(2) {
+        if pair[1] > pair[0] {
+            rises += pair[1] - pair[0];
+        } else {
+            falls += pair[0] - pair[1];
+        }
+    }
+    // Whichever way the line mostly runs, everything against the grain counts against it
+    -(rises.min(falls) as i64) as f64
+}
+
+/// How far apart neighbouring tiles are, ignoring gaps. Lines of equal tiles score 0.
+fn line_smoothness(line: &[u32]) -> f64 {
+    let tiles = line.iter().filter(|exponent| **exponent != 0).collect::<Vec<_>>();
+    -(tiles.windows(2).map(|pair| pair[0].abs_diff(*pair[1])).sum::<u32>() as i64) as f64
+}
+
+/// The number of pairs of equal tiles that are next to each other, ignoring gaps, each of which could be merged
+/// by a single move
+fn line_merge_potential(line: &[u32]) -> f64 {
+    let tiles = line.iter().filter(|exponent| **exponent != 0).collect::<Vec<_>>();
+    tiles.windows(2).filter(|pair| pair[0] == pair[1]).count() as f64
+}
+
+/// How much each term of an `Evaluation` counts towards the total
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct EvaluationWeights {
+    pub empty_cells: f64,
+    pub monotonicity: f64,
+    pub smoothness: f64,
+    pub max_tile_in_corner: f64,
+    pub merge_potential: f64,
+}
+
+impl Default for EvaluationWeights {
+    /// Found by letting the expectimax player try out a handful of combinations on the classic rules
+    fn default() -> Self {
+        Self {
+            empty_cells: 270.0,
+            monotonicity: 47.0,
+            smoothness: 10.0,
+            max_tile_in_corner: 1000.0,
+            merge_potential: 300.0,
+        }
+    }
+}
+
+/// The terms that make up the score of a position, before they're weighted. Higher is better for every term.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Evaluation {
+    /// The number of free cells
+    pub empty_cells: f64,
+    /// How far each row and column is from running steadily up or down, as a penalty (0 at best)
+    pub monotonicity: f64,
+    /// The total difference between neighbouring tiles, as a penalty (0 at best)
+    pub smoothness: f64,
+    /// 1 if the largest tile sits in a corner, otherwise 0
+    pub max_tile_in_corner: f64,
+    /// The number of neighbouring pairs of tiles that could be merged
+    pub merge_potential: f64,
+}
+
+impl Evaluation {
+    pub fn of(board: &Board) -> Self {
+        let lines = lines(board);
+        let (width, height) = (board.width(), board.height());
+        let corners = [(0, 0), (width - 1, 0), (0, height - 1), (width - 1, height - 1)];
+        let max_tile = board.max_tile();
+        let max_tile_in_corner = max_tile > 0 && corners.into_iter().any(|(col_idx, row_idx)| {
+            board.contents_at(BoardCoordinate(col_idx, row_idx)) == CellContents::Occupied(max_tile)
+        });
+        Self {
+            empty_cells: board.cells.iter().filter(|cell| cell.is_empty()).count() as f64,
+            monotonicity: lines.iter().map(|line| line_monotonicity(line)).sum(),
+            smoothness: lines.iter().map(|line| line_smoothness(line)).sum(),
+            max_tile_in_corner: if max_tile_in_corner { 1.0 } else { 0.0 },
+            merge_potential: lines.iter().map(|line| line_merge_potential(line)).sum(),
+        }
+    }
+
+    /// Each term along with its weight, in the order they're listed on the struct
+    fn weighted_terms(&self, weights: &EvaluationWeights) -> [(&'static str, f64, f64); 5] {
+        [
+            ("Empty cells", self.empty_cells, weights.empty_cells),
+            ("Monotonicity", self.monotonicity, weights.monotonicity),
+            ("Smoothness", self.smoothness, weights.smoothness),
+            ("Max tile in corner", self.max_tile_in_corner, weights.max_tile_in_corner),
+            ("Merge potential", self.merge_potential, weights.merge_potential),
+        ]
+    }
+
+    /// Combines the terms into a single score
+    pub fn total(&self, weights: &EvaluationWeights) -> f64 {
+        self.weighted_terms(weights).iter().map(|(_, term, weight)| term * weight).sum()
+    }
+
+    /// A table of each term, its weight and what it contributes, followed by the total
+    pub fn breakdown(&self, weights: &EvaluationWeights) -> String {
+        let mut out = String::new();
+        for (name, term, weight) in self.weighted_terms(weights) {
+            out.push_str(&format!("{name: <20}{term: >8} x {weight: <8}{: >10.1}\n", term * weight));
+        }
+        out.push_str(&format!("{: <40}{: >10.1}", "Total", self.total(weights)));
+        out
+    }
+}
@@ -0,0 +1,158 @@
+//! A player that searches ahead with expectimax: at each of its own turns it takes the move with the best value,
+//! and at each spawn it takes the average over every free cell and every value in the spawn table, weighted by how
+//! likely each one is.
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::{Board, CellContents, Direction, Evaluation, EvaluationWeights, Strategy};
+
+/// How many moves ahead `best_move` looks
+pub const DEFAULT_SEARCH_DEPTH: usize = 3;
+
+/// What a position is worth when there's no move left to make. This is far below anything a live position can be
+/// worth, so the search steers away from losing whenever it can.
+const LOST_GAME_VALUE: f64 = -1_000_000.0;
+
+/// The best move on this board, found by an expectimax search `DEFAULT_SEARCH_DEPTH` moves deep
+pub fn best_move(board: &Board) -> Direction {
+    Expectimax::default().choose(board)
+}
+
+/// The tiles on the board, with 0 for an empty cell, so that positions reached in different ways can share an entry
+/// in the transposition cache
+fn position_key(board: &Board) -> Vec<usize> {
+    board.cells.iter().map(|cell| match cell.contents {
+        CellContents::Empty => 0,
+        CellContents::Occupied(value) => value,
+    }).collect()
+}
+
+/// An expectimax search with a limit on its depth, and optionally on how long it may take over each move
+#[derive(Debug, Clone)]
+pub struct Expectimax {
+    /// How many of the player's moves to look ahead, counting the one being chosen
+    pub depth: usize,
+    /// When set, the search deepens one move at a time and settles for the deepest search it finished within the
+    /// budget. Note that this makes the chosen move depend on how fast the machine is.
+    pub time_budget: Option<Duration>,
+    /// Scores the positions at the end of the search. The points earned along the way are added on top.
+    pub weights: EvaluationWeights,
+    /// The value of each position already searched in the current move, keyed by its tiles and the remaining depth
+    cache: HashMap<(Vec<usize>, usize), f64>,
+    deadline: Option<Instant>,
+    out_of_time: bool,
+}
+
+impl Default for Expectimax {
+    fn default() -> Self {
+        Self::with_depth(DEFAULT_SEARCH_DEPTH)
+    }
+}
+
+impl Expectimax {
+    pub fn with_depth(depth: usize) -> Self {
+        Self {
+            depth,
+            time_budget: None,
+            weights: EvaluationWeights::default(),
+            cache: HashMap::new(),
+            deadline: None,
+            out_of_time: false,
+        }
+    }
+
+    /// Searches up to `depth` moves ahead, but stops deepening once `time_budget` has passed
+    pub fn with_time_budget(depth: usize, time_budget: Duration) -> Self {
+        Self {
+            time_budget: Some(time_budget),
+            ..Self::with_depth(depth)
+        }
+    }
+
+    /// The value of the player's best move, `depth` moves from the end of the search
+    fn max_value(&mut self, board: &Board, depth: usize) -> f64 {
+        if depth == 0 {
+            return Evaluation::of(board).total(&self.weights);
+        }
+        let key = (position_key(board), depth);
+        if let Some(value) = self.cache.get(&key) {
+            return *value;
+        }
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            // Whatever we return now will be thrown away along with the rest of this round of the search
+            self.out_of_time = true;
+            return 0.0;
+        }
+        let value = Direction::ALL.into_iter()
+            .filter_map(|direction| self.move_value(board, direction, depth))
+            .max_by(f64::total_cmp)
+            .unwrap_or(LOST_GAME_VALUE);
+        if !self.out_of_time {
+            self.cache.insert(key, value);
+        }
+        value
+    }
+
+    /// The points earned by the move, plus the expected value of the position it leads to. Returns `None` if the
+    /// move doesn't change the board.
+    fn move_value(&mut self, board: &Board, direction: Direction, depth: usize) -> Option<f64> {
+        let mut after_move = board.clone();
+        let outcome = after_move.press(direction);
+        if !outcome.board_changed() {
+            return None;
+        }
+        let spawns = after_move.rules.tiles_per_turn;
+        Some(outcome.score_delta as f64 + self.chance_value(&after_move, spawns, depth))
+    }
+
+    /// The expected value of the position once `spawns_left` more tiles have appeared
+    fn chance_value(&mut self, board: &Board, spawns_left: usize, depth: usize) -> f64 {
+        let free_cells = board.cells.iter().filter(|cell| cell.is_empty()).map(|cell| cell.coords).collect::<Vec<_>>();
+        if spawns_left == 0 || free_cells.is_empty() {
+            return self.max_value(board, depth - 1);
+        }
+        let spawn_table = board.rules.spawn_table.iter().filter(|spawn| spawn.weight > 0).copied().collect::<Vec<_>>();
+        let total_weight = spawn_table.iter().map(|spawn| spawn.weight as f64).sum::<f64>();
+        let mut expected_value = 0.0;
+        for coords in free_cells.iter() {
+            for spawn in spawn_table.iter() {
+                let mut after_spawn = board.clone();
+                after_spawn.set_contents_at(*coords, CellContents::Occupied(spawn.value));
+                let probability = spawn.weight as f64 / total_weight / free_cells.len() as f64;
+                expected_value += probability * self.chance_value(&after_spawn, spawns_left - 1, depth);
+            }
+        }
+        expected_value
+    }
+
+    /// The best move found by searching exactly `depth` moves ahead, or `None` if the search ran out of time
+    fn search(&mut self, board: &Board, depth: usize) -> Option<Direction> {
+        let best = Direction::ALL.into_iter()
+            .filter_map(|direction| Some((direction, self.move_value(board, direction, depth)?)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(direction, _)| direction);
+        if self.out_of_time {
+            return None;
+        }
+        best
+    }
+}
+
+impl Strategy for Expectimax {
+    fn choose(&mut self, board: &Board) -> Direction {
+        self.cache.clear();
+        self.out_of_time = false;
+        self.deadline = self.time_budget.map(|budget| Instant::now() + budget);
+        // With no time limit there's no point working up to the full depth
+        let first_depth = if self.deadline.is_some() { 1 } else { self.depth.max(1) };
+        // Fall back on any legal move if not even the shallowest search finishes in time
+        let mut best = board.legal_moves().first().copied().unwrap_or(Direction::Left);
+        for depth in first_depth..=self.depth.max(1) {
+            match self.search(board, depth) {
+                Some(direction) => best = direction,
+                None => break,
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+
+use crate::Board;
+
+/// How many moves can be taken back by default
+pub const DEFAULT_HISTORY_LIMIT: usize = 64;
+
+/// Snapshots of the board taken before each move, so that moves can be taken back and replayed.
+/// Each snapshot includes the state of the tile generator, so redoing a move (or making it again after an undo)
+/// spawns exactly the same tiles as the first time around.
+#[derive(Debug, Clone)]
+pub struct History {
+    undo_stack: VecDeque<Board>,
+    redo_stack: Vec<Board>,
+    limit: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::with_limit(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: vec![],
+            limit,
+        }
+    }
+
+    /// Remembers the state of the board just before a move is made
+    pub fn record(&mut self, board_before_move: Board) {
+        // Making a fresh move abandons whatever was previously undone
+        self.redo_stack.clear();
+        self.undo_stack.push_back(board_before_move);
+        while self.undo_stack.len() > self.limit {
+            // Forget the oldest moves once we're over the limit
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Returns whether there was anything to undo
+    pub fn undo(&mut self, board: &mut Board) -> bool {
+        let Some(previous) = self.undo_stack.pop_back() else {
+            return false;
+        };
+        let current = std::mem::replace(board, previous);
+        board.keep_best_score_from(&current);
+        self.redo_stack.push(current);
+        true
+    }
+
+    /// Returns whether there was anything to redo
+    pub fn redo(&mut self, board: &mut Board) -> bool {
+        let Some(next) = self.redo_stack.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(board, next);
+        board.keep_best_score_from(&current);
+        self.undo_stack.push_back(current);
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
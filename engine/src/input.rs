@@ -1,11 +1,17 @@
-#[derive(Debug, Copy, Clone)]
-pub(crate) enum Direction {
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum Direction {
     Left,
     Right,
     Up,
     Down
 }
 
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Left, Direction::Right, Direction::Up, Direction::Down];
+}
+
 impl TryFrom<&str> for Direction {
     type Error = ();
 
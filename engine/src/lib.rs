@@ -0,0 +1,36 @@
+//! The 2048 game engine shared by the native (stdin) and wasm front-ends.
+mod baseline;
+mod bitboard;
+mod board;
+mod evaluation;
+mod expectimax;
+mod history;
+mod input;
+mod monte_carlo;
+mod notation;
+mod ntuple;
+mod outcome;
+mod packed_replay;
+mod replay;
+mod rules;
+mod save;
+mod strategy;
+mod tiles;
+mod transcript;
+
+pub use baseline::{CornerPlayer, CyclicPlayer, GreedyPlayer, RandomPlayer};
+pub use bitboard::{Bitboard, BITBOARD_SIZE, MAX_BITBOARD_EXPONENT};
+pub use board::{Board, BoardCoordinate, Cell, CellContents, DEFAULT_BOARD_SIZE, DEFAULT_GOAL_TILE, STARTING_TILE_COUNT};
+pub use evaluation::{Evaluation, EvaluationWeights};
+pub use expectimax::{best_move, Expectimax, DEFAULT_SEARCH_DEPTH};
+pub use history::{History, DEFAULT_HISTORY_LIMIT};
+pub use input::Direction;
+pub use monte_carlo::{MonteCarlo, RolloutObjective, DEFAULT_ROLLOUT_COUNT, DEFAULT_ROLLOUT_DEPTH};
+pub use ntuple::{NTupleNetwork, NTuplePlayer, WeightsError, DEFAULT_LEARNING_RATE, NTUPLE_FORMAT_VERSION};
+pub use outcome::{Merge, MoveOutcome, Slide};
+pub use packed_replay::{ClaimedResult, PACKED_REPLAY_VERSION};
+pub use replay::{Replay, ReplayError, ReplayMove, ReplayOutcome, REPLAY_FORMAT_VERSION};
+pub use rules::{Rules, SpawnWeight, MAX_BOARD_CELLS};
+pub use save::{LoadError, SAVE_FORMAT_VERSION};
+pub use strategy::Strategy;
+pub use tiles::{Tile, TileEvent, TileId};
@@ -0,0 +1,108 @@
+//! A player that tries each move out by playing a batch of random games from the position it leads to, and takes
+//! whichever move did best on average. It's much cheaper than a full search, and gets stronger the more rollouts
+//! it's given.
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Board, Direction, Strategy};
+
+/// How many random games are played out from each move
+pub const DEFAULT_ROLLOUT_COUNT: usize = 50;
+/// How many moves each random game runs for before it's judged, if it hasn't already ended
+pub const DEFAULT_ROLLOUT_DEPTH: usize = 20;
+
+/// What the random games are judged on
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum RolloutObjective {
+    /// The score at the end of the game
+    #[default]
+    Score,
+    /// The largest tile at the end of the game
+    MaxTile,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonteCarlo {
+    /// How many random games to play from each move
+    pub rollouts: usize,
+    /// How many moves each random game may run for. Pass `usize::MAX` to play every game out to the end.
+    pub rollout_depth: usize,
+    pub objective: RolloutObjective,
+    /// Picks the random moves, and the seeds for the tiles spawned during each game. Two players starting from the
+    /// same seed choose the same moves.
+    rng: ChaCha8Rng,
+}
+
+impl Default for MonteCarlo {
+    fn default() -> Self {
+        Self::with_seed(thread_rng().gen())
+    }
+}
+
+impl MonteCarlo {
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rollouts: DEFAULT_ROLLOUT_COUNT,
+            rollout_depth: DEFAULT_ROLLOUT_DEPTH,
+            objective: RolloutObjective::default(),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn with_rollouts(self, rollouts: usize, rollout_depth: usize) -> Self {
+        Self {
+            rollouts,
+            rollout_depth,
+            ..self
+        }
+    }
+
+    pub fn with_objective(self, objective: RolloutObjective) -> Self {
+        Self {
+            objective,
+            ..self
+        }
+    }
+
+    /// Plays random moves from the given board (just after a move, before its tiles have spawned), and returns how
+    /// the game ended up
+    fn rollout(&mut self, after_move: &Board) -> f64 {
+        let mut board = after_move.clone();
+        // The game's own generator would spawn the same tiles in every rollout
+        board.reseed(self.rng.gen());
+        board.spawn_tiles_for_turn();
+        for _ in 0..self.rollout_depth {
+            let mut directions = Direction::ALL;
+            directions.shuffle(&mut self.rng);
+            // Pressing in a direction that doesn't move anything leaves the board as it was, so just keep trying
+            if !directions.into_iter().any(|direction| board.press(direction).board_changed()) {
+                break;
+            }
+            board.spawn_tiles_for_turn();
+        }
+        match self.objective {
+            RolloutObjective::Score => board.score() as f64,
+            RolloutObjective::MaxTile => board.max_tile() as f64,
+        }
+    }
+
+    /// The average result of the random games played after pressing `direction`, or `None` if the move doesn't
+    /// change the board
+    fn mean_result(&mut self, board: &Board, direction: Direction) -> Option<f64> {
+        let mut after_move = board.clone();
+        if !after_move.press(direction).board_changed() {
+            return None;
+        }
+        let total = (0..self.rollouts.max(1)).map(|_| self.rollout(&after_move)).sum::<f64>();
+        Some(total / self.rollouts.max(1) as f64)
+    }
+}
+
+impl Strategy for MonteCarlo {
+    fn choose(&mut self, board: &Board) -> Direction {
+        Direction::ALL.into_iter()
+            .filter_map(|direction| Some((direction, self.mean_result(board, direction)?)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(Direction::Left, |(direction, _)| direction)
+    }
+}
@@ -0,0 +1,84 @@
+//! A compact, single-line way of writing down a board, for pasting into bug reports and tests.
+//!
+//! Rows are listed from the top and separated by `/`. Within a row, each cell is a single character: `.` for an
+//! empty cell, or the tile's exponent as a base-36 digit (`1` is a 2, `a` is a 1024, `b` is a 2048, and so on).
+//! For example, `1.2./..3./..../...b` describes
+//!
+//! ```text
+//! |  2 |    |  4 |    |
+//! |    |    |  8 |    |
+//! |    |    |    |    |
+//! |    |    |    |2048|
+//! ```
+use std::str::FromStr;
+
+use crate::{Board, BoardCoordinate, CellContents, Rules};
+
+const ROW_SEPARATOR: char = '/';
+const EMPTY_CELL: char = '.';
+const EXPONENT_RADIX: u32 = 36;
+
+impl Board {
+    /// Writes the tiles on the board in the notation described in `notation.rs`. Everything else about the game
+    /// (the score, the rules, the seed) is left out.
+    pub fn to_notation(&self) -> String {
+        self.cells.chunks(self.width()).map(|row| {
+            row.iter().map(|cell| match cell.contents {
+                CellContents::Empty => EMPTY_CELL,
+                CellContents::Occupied(value) => {
+                    assert!(value >= 2 && value.is_power_of_two(), "{value} at {} has no notation", cell.coords);
+                    char::from_digit(value.trailing_zeros(), EXPONENT_RADIX)
+                        .unwrap_or_else(|| panic!("{value} at {} is too large to write down", cell.coords))
+                }
+            }).collect::<String>()
+        }).collect::<Vec<_>>().join(&ROW_SEPARATOR.to_string())
+    }
+}
+
+fn contents_from_notation(c: char) -> Option<CellContents> {
+    // A 0 exponent would be a tile of 1, so read it as an empty cell too
+    if c == EMPTY_CELL || c == '0' {
+        return Some(CellContents::Empty);
+    }
+    let exponent = c.to_digit(EXPONENT_RADIX)?;
+    Some(CellContents::Occupied(1usize.checked_shl(exponent)?))
+}
+
+impl FromStr for Board {
+    type Err = ();
+
+    /// The size of the board is taken from the notation, and the rest of the rules are the classic ones. The board
+    /// always starts from seed 0, so that the same notation reproduces the same game: reseed it if that's not wanted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s.trim().split(ROW_SEPARATOR).map(|row| row.trim().chars().collect::<Vec<_>>()).collect::<Vec<_>>();
+        let width = rows[0].len();
+        if width == 0 || rows.iter().any(|row| row.len() != width) {
+            // Every row needs the same, non-zero, number of cells
+            return Err(());
+        }
+        let mut board = Board::with_seed_and_rules(0, Rules::classic().with_size(width, rows.len()));
+        for (row_idx, row) in rows.iter().enumerate() {
+            for (col_idx, c) in row.iter().enumerate() {
+                board.set_contents_at(BoardCoordinate(col_idx, row_idx), contents_from_notation(*c).ok_or(())?);
+            }
+        }
+        Ok(board)
+    }
+}
+
+/// Builds a board from its notation, panicking if the notation is invalid. Rows can be given either as a single
+/// string, or as one string per row:
+///
+/// ```
+/// use engine::board;
+///
+/// let board = board!("1.2./..3./..../...b");
+/// assert_eq!(board.to_notation(), board!("1.2.", "..3.", "....", "...b").to_notation());
+/// ```
+#[macro_export]
+macro_rules! board {
+    ($($row:expr),+ $(,)?) => {{
+        let notation = [$($row),+].join("/");
+        notation.parse::<$crate::Board>().unwrap_or_else(|_| panic!("Invalid board notation: {notation}"))
+    }};
+}
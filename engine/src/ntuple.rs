@@ -0,0 +1,267 @@
+//! An n-tuple network: a learned evaluation of 4x4 positions, made up of lookup tables indexed by the tiles in a
+//! handful of small groups of cells (the tuples). Each tuple is looked up in all eight orientations of the board,
+//! so that what's learned in one corner carries over to the others.
+//!
+//! The network is trained by temporal-difference learning on the positions just after each move, before the new
+//! tile appears, and is saved in the following binary layout (all integers little-endian):
+//!
+//! ```text
+//! magic          4 bytes   "NTUP"
+//! version        u8
+//! games trained  u64
+//! tuple count    u8        followed, for each tuple, by its length (u8) and the index (u8) of each of its cells
+//! weights        f32       one table per tuple, of 16^length entries each
+//! ```
+use std::fmt::{Display, Formatter};
+
+use crate::{Bitboard, Board, Direction, GreedyPlayer, Strategy, BITBOARD_SIZE};
+
+const MAGIC: &[u8; 4] = b"NTUP";
+/// Bumped whenever the layout of the weight file changes
+pub const NTUPLE_FORMAT_VERSION: u8 = 1;
+/// How quickly the network moves towards what it's just seen, shared between all the weights that each position
+/// looks up
+pub const DEFAULT_LEARNING_RATE: f32 = 0.1;
+
+const CELL_COUNT: usize = BITBOARD_SIZE * BITBOARD_SIZE;
+/// Each cell can hold one of 16 exponents
+const EXPONENTS_PER_CELL: usize = 16;
+
+/// The cells (indexed row by row from the top-left) in each tuple of a fresh network: the outer and inner rows,
+/// and the squares in the corner and on the edge
+const DEFAULT_TUPLES: [[u8; 4]; 4] = [
+    [0, 1, 2, 3],
+    [4, 5, 6, 7],
+    [0, 1, 4, 5],
+    [1, 2, 5, 6],
+];
+
+/// Why a weight file couldn't be read
+#[derive(Debug, PartialEq, Clone)]
+pub enum WeightsError {
+    Malformed(String),
+    UnsupportedVersion(u8),
+}
+
+impl Display for WeightsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(reason) => write!(f, "the weight file is malformed: {reason}"),
+            Self::UnsupportedVersion(version) => write!(f, "weight file version {version} isn't supported"),
+        }
+    }
+}
+
+/// Moves a cell to where it ends up in each of the eight ways the board can be rotated or reflected
+fn symmetries_of(cell_idx: u8) -> [u8; 8] {
+    let last = BITBOARD_SIZE - 1;
+    let (col_idx, row_idx) = (cell_idx as usize % BITBOARD_SIZE, cell_idx as usize / BITBOARD_SIZE);
+    let mut moved = [0; 8];
+    for (i, moved_cell) in moved.iter_mut().enumerate() {
+        let (mut x, mut y) = (col_idx, row_idx);
+        if i & 0b001 != 0 {
+            x = last - x;
+        }
+        if i & 0b010 != 0 {
+            y = last - y;
+        }
+        if i & 0b100 != 0 {
+            (x, y) = (y, x);
+        }
+        *moved_cell = (x + (y * BITBOARD_SIZE)) as u8;
+    }
+    moved
+}
+
+fn exponent_at(bitboard: Bitboard, cell_idx: u8) -> usize {
+    ((bitboard.0 >> (cell_idx as u32 * 4)) & 0xf) as usize
+}
+
+/// One tuple, along with its lookup table
+#[derive(Debug, PartialEq, Clone)]
+struct Tuple {
+    cells: Vec<u8>,
+    /// The cells of the tuple in each orientation of the board
+    orientations: Vec<Vec<u8>>,
+    weights: Vec<f32>,
+}
+
+impl Tuple {
+    fn new(cells: Vec<u8>, weights: Vec<f32>) -> Self {
+        let moved = cells.iter().map(|cell_idx| symmetries_of(*cell_idx)).collect::<Vec<_>>();
+        let orientations = (0..8).map(|i| moved.iter().map(|symmetries| symmetries[i]).collect()).collect();
+        Self { cells, orientations, weights }
+    }
+
+    /// Where each orientation of the tuple looks up its weight on this board
+    fn indexes(&self, bitboard: Bitboard) -> impl Iterator<Item = usize> + '_ {
+        self.orientations.iter().map(move |cells| {
+            cells.iter().fold(0, |index, cell_idx| (index * EXPONENTS_PER_CELL) + exponent_at(bitboard, *cell_idx))
+        })
+    }
+}
+
+/// Reads fields off the front of a weight file, failing if it runs out
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], WeightsError> {
+        if self.bytes.len() < N {
+            return Err(WeightsError::Malformed("the file is cut short".to_string()));
+        }
+        let (taken, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        Ok(taken.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, WeightsError> {
+        Ok(self.take::<1>()?[0])
+    }
+}
+
+/// A learned evaluation of 4x4 positions. A fresh network values every position at 0.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NTupleNetwork {
+    tuples: Vec<Tuple>,
+    /// How many games of self-play have gone into the weights, kept in the weight file for reference
+    pub games_trained: u64,
+}
+
+impl Default for NTupleNetwork {
+    fn default() -> Self {
+        let tuples = DEFAULT_TUPLES.iter().map(|cells| {
+            Tuple::new(cells.to_vec(), vec![0.0; EXPONENTS_PER_CELL.pow(cells.len() as u32)])
+        }).collect();
+        Self { tuples, games_trained: 0 }
+    }
+}
+
+impl NTupleNetwork {
+    /// The expected number of points still to come from this position, judging by what the network has learned
+    pub fn value(&self, bitboard: Bitboard) -> f32 {
+        self.tuples.iter().map(|tuple| tuple.indexes(bitboard).map(|index| tuple.weights[index]).sum::<f32>()).sum()
+    }
+
+    /// Moves the value of the position towards `target`
+    fn learn(&mut self, bitboard: Bitboard, target: f32, learning_rate: f32) {
+        let lookups = self.tuples.iter().map(|tuple| tuple.orientations.len()).sum::<usize>();
+        // Share the correction out between every weight the position looks up
+        let step = learning_rate * (target - self.value(bitboard)) / lookups as f32;
+        for tuple in self.tuples.iter_mut() {
+            let indexes = tuple.indexes(bitboard).collect::<Vec<_>>();
+            for index in indexes {
+                tuple.weights[index] += step;
+            }
+        }
+    }
+
+    /// The move with the most points now plus points to come, along with those points and the position it leaves
+    /// (before any tile spawns). Returns `None` if no move changes the board.
+    pub fn best_move(&self, bitboard: Bitboard) -> Option<(Direction, usize, Bitboard)> {
+        Direction::ALL.into_iter()
+            .map(|direction| {
+                let (after_move, points) = bitboard.press(direction);
+                (direction, points, after_move)
+            })
+            .filter(|(_, _, after_move)| *after_move != bitboard)
+            .max_by(|(_, a_points, a), (_, b_points, b)| {
+                (*a_points as f32 + self.value(*a)).total_cmp(&(*b_points as f32 + self.value(*b)))
+            })
+    }
+
+    /// Plays a game out on `board` by always taking the best move the network knows of, learning from each move as
+    /// it goes. The board must be 4x4, and the game stops early if a tile grows too large to fit in a `Bitboard`.
+    pub fn train_on_game(&mut self, board: &mut Board, learning_rate: f32) {
+        let mut previous_after_move: Option<Bitboard> = None;
+        while let Ok(bitboard) = Bitboard::try_from(&*board) {
+            let Some((direction, points, after_move)) = self.best_move(bitboard) else {
+                break;
+            };
+            // The last position we left is worth what this move scored, plus whatever this one's worth
+            if let Some(previous) = previous_after_move {
+                self.learn(previous, points as f32 + self.value(after_move), learning_rate);
+            }
+            previous_after_move = Some(after_move);
+            board.press(direction);
+            board.spawn_tiles_for_turn();
+        }
+        // There are no points to come after the last move of the game
+        if let Some(previous) = previous_after_move {
+            self.learn(previous, 0.0, learning_rate);
+        }
+        self.games_trained += 1;
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(NTUPLE_FORMAT_VERSION);
+        bytes.extend(self.games_trained.to_le_bytes());
+        bytes.push(self.tuples.len() as u8);
+        for tuple in self.tuples.iter() {
+            bytes.push(tuple.cells.len() as u8);
+            bytes.extend(tuple.cells.iter());
+        }
+        for tuple in self.tuples.iter() {
+            for weight in tuple.weights.iter() {
+                bytes.extend(weight.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WeightsError> {
+        let mut reader = Reader { bytes };
+        if &reader.take::<4>()? != MAGIC {
+            return Err(WeightsError::Malformed("not an n-tuple weight file".to_string()));
+        }
+        let version = reader.u8()?;
+        if version != NTUPLE_FORMAT_VERSION {
+            return Err(WeightsError::UnsupportedVersion(version));
+        }
+        let games_trained = u64::from_le_bytes(reader.take()?);
+        let shapes = (0..reader.u8()?).map(|_| {
+            let cells = (0..reader.u8()?).map(|_| reader.u8()).collect::<Result<Vec<_>, _>>()?;
+            if cells.is_empty() || cells.len() > 8 || cells.iter().any(|cell_idx| *cell_idx as usize >= CELL_COUNT) {
+                return Err(WeightsError::Malformed(format!("{cells:?} isn't a tuple of cells on the board")));
+            }
+            Ok(cells)
+        }).collect::<Result<Vec<_>, _>>()?;
+
+        let mut tuples = vec![];
+        for cells in shapes {
+            let weights = (0..EXPONENTS_PER_CELL.pow(cells.len() as u32))
+                .map(|_| Ok(f32::from_le_bytes(reader.take()?)))
+                .collect::<Result<Vec<_>, WeightsError>>()?;
+            tuples.push(Tuple::new(cells, weights));
+        }
+        if !reader.bytes.is_empty() {
+            return Err(WeightsError::Malformed("there's more data than the tuples call for".to_string()));
+        }
+        Ok(Self { tuples, games_trained })
+    }
+}
+
+/// Plays whichever move a trained `NTupleNetwork` rates best. The network only knows 4x4 boards, so on any other
+/// board (or once a tile outgrows a `Bitboard`) this falls back on `GreedyPlayer`.
+#[derive(Debug, Clone)]
+pub struct NTuplePlayer {
+    pub network: NTupleNetwork,
+}
+
+impl NTuplePlayer {
+    pub fn new(network: NTupleNetwork) -> Self {
+        Self { network }
+    }
+}
+
+impl Strategy for NTuplePlayer {
+    fn choose(&mut self, board: &Board) -> Direction {
+        let best = Bitboard::try_from(board).ok().and_then(|bitboard| self.network.best_move(bitboard));
+        match best {
+            Some((direction, _, _)) => direction,
+            None => GreedyPlayer.choose(board),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{BoardCoordinate, Direction};
+
+/// Two equal tiles that were combined into a single tile of double the value
+#[derive(Debug, PartialEq, Clone)]
+pub struct Merge {
+    /// Where the two tiles started out before the move. The first tile is the one nearer the edge being pushed towards.
+    pub sources: (BoardCoordinate, BoardCoordinate),
+    /// Where the combined tile ended up
+    pub destination: BoardCoordinate,
+    pub value: usize,
+}
+
+/// A tile that changed position without taking part in a merge
+#[derive(Debug, PartialEq, Clone)]
+pub struct Slide {
+    pub from: BoardCoordinate,
+    pub to: BoardCoordinate,
+}
+
+/// Everything that happened to the board in response to a single press
+#[derive(Debug, PartialEq, Clone)]
+pub struct MoveOutcome {
+    pub direction: Direction,
+    pub merges: Vec<Merge>,
+    pub slides: Vec<Slide>,
+    /// Points earned by this move: the sum of the values of all the merged tiles
+    pub score_delta: usize,
+    /// Set when this move created the goal tile for the first time in the current game
+    pub reached_goal: bool,
+}
+
+impl MoveOutcome {
+    pub(crate) fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            merges: vec![],
+            slides: vec![],
+            score_delta: 0,
+            reached_goal: false,
+        }
+    }
+
+    pub fn board_changed(&self) -> bool {
+        // A merge can happen without anything sliding (e.g. a pair already sat against the edge), so check both
+        !self.merges.is_empty() || !self.slides.is_empty()
+    }
+}
+
+impl Display for MoveOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.board_changed() {
+            return write!(f, "{:?}: nothing moved", self.direction);
+        }
+        write!(
+            f,
+            "{:?}: {} slid, {} merged, +{} points",
+            self.direction,
+            self.slides.len(),
+            self.merges.len(),
+            self.score_delta,
+        )?;
+        for merge in self.merges.iter() {
+            let (first, second) = merge.sources;
+            write!(f, "\n  {first} + {second} -> {} at {}", merge.value, merge.destination)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+//! A compact binary encoding of replays, for games long enough that the JSON gets unwieldy.
+//!
+//! The layout (all integers little-endian) is:
+//!
+//! ```text
+//! magic        4 bytes   "2048"
+//! version      u8
+//! seed         u64
+//! width        u16
+//! height       u16
+//! goal tile    u8        as an exponent
+//! tiles/turn   u16
+//! spawn count  u8        followed by that many (exponent: u8, weight: u32) pairs
+//! move count   u32
+//! moves        2 bits per move, four to a byte, starting from the least significant bits
+//! ```
+//!
+//! Timestamps and the recorded outcome are left out: a replay in this form is just the game itself, and is meant
+//! to be checked against a claimed result with `Replay::verify`.
+use std::fmt::{Display, Formatter};
+
+use crate::{Direction, Replay, ReplayError, Rules, SpawnWeight};
+
+const MAGIC: &[u8; 4] = b"2048";
+/// Bumped whenever the binary layout changes
+pub const PACKED_REPLAY_VERSION: u8 = 1;
+const MOVES_PER_BYTE: usize = 4;
+
+/// A result submitted for a game, to be checked against its replay
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct ClaimedResult {
+    pub score: usize,
+    pub max_tile: usize,
+}
+
+impl Display for ClaimedResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} points (largest tile {})", self.score, self.max_tile)
+    }
+}
+
+fn direction_code(direction: Direction) -> u8 {
+    match direction {
+        Direction::Left => 0,
+        Direction::Right => 1,
+        Direction::Up => 2,
+        Direction::Down => 3,
+    }
+}
+
+fn direction_from_code(code: u8) -> Direction {
+    match code & 0b11 {
+        0 => Direction::Left,
+        1 => Direction::Right,
+        2 => Direction::Up,
+        _ => Direction::Down,
+    }
+}
+
+fn exponent_of(value: usize) -> u8 {
+    assert!(value.is_power_of_two(), "{value} can't be stored as an exponent");
+    value.trailing_zeros() as u8
+}
+
+/// Reads fields off the front of the encoded replay, failing if it runs out
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        if self.bytes.len() < N {
+            return Err(ReplayError::Malformed("the replay is cut short".to_string()));
+        }
+        let (taken, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        Ok(taken.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, ReplayError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ReplayError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, ReplayError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, ReplayError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    /// A power of two, stored as its exponent
+    fn power_of_two(&mut self) -> Result<usize, ReplayError> {
+        1usize.checked_shl(self.u8()? as u32).ok_or_else(|| ReplayError::Malformed("a tile is too large".to_string()))
+    }
+}
+
+impl Replay {
+    /// Packs the replay into the binary form described in `packed_replay.rs`. Panics if the rules are too large
+    /// to fit in the header, which no playable game comes near.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let rules = &self.rules;
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(PACKED_REPLAY_VERSION);
+        bytes.extend(self.seed.to_le_bytes());
+        bytes.extend(u16::try_from(rules.width).expect("Board too wide to pack").to_le_bytes());
+        bytes.extend(u16::try_from(rules.height).expect("Board too tall to pack").to_le_bytes());
+        bytes.push(exponent_of(rules.goal_tile));
+        bytes.extend(u16::try_from(rules.tiles_per_turn).expect("Too many tiles per turn to pack").to_le_bytes());
+        bytes.push(u8::try_from(rules.spawn_table.len()).expect("Spawn table too long to pack"));
+        for spawn in rules.spawn_table.iter() {
+            bytes.push(exponent_of(spawn.value));
+            bytes.extend(spawn.weight.to_le_bytes());
+        }
+        bytes.extend(u32::try_from(self.moves.len()).expect("Too many moves to pack").to_le_bytes());
+        for chunk in self.moves.chunks(MOVES_PER_BYTE) {
+            bytes.push(chunk.iter().enumerate().fold(0, |byte, (i, replay_move)| {
+                byte | (direction_code(replay_move.direction) << (i * 2))
+            }));
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut reader = Reader { bytes };
+        if &reader.take::<4>()? != MAGIC {
+            return Err(ReplayError::Malformed("not a packed replay".to_string()));
+        }
+        let version = reader.u8()?;
+        if version != PACKED_REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version as u32));
+        }
+        let seed = reader.u64()?;
+        let width = reader.u16()? as usize;
+        let height = reader.u16()? as usize;
+        let goal_tile = reader.power_of_two()?;
+        let tiles_per_turn = reader.u16()? as usize;
+        let spawn_table = (0..reader.u8()?).map(|_| {
+            Ok(SpawnWeight { value: reader.power_of_two()?, weight: reader.u32()? })
+        }).collect::<Result<Vec<_>, ReplayError>>()?;
+        let rules = Rules { width, height, goal_tile, spawn_table, tiles_per_turn };
+        if !rules.is_playable() {
+            // Turn away a header asking for an enormous board before anything tries to build it
+            return Err(ReplayError::InvalidRules);
+        }
+
+        let move_count = reader.u32()? as usize;
+        let packed_moves = reader.bytes;
+        if packed_moves.len() != move_count.div_ceil(MOVES_PER_BYTE) {
+            return Err(ReplayError::Malformed(format!("expected {move_count} moves")));
+        }
+        let mut replay = Self::new(seed, rules);
+        for move_idx in 0..move_count {
+            let byte = packed_moves[move_idx / MOVES_PER_BYTE];
+            replay.record(direction_from_code(byte >> ((move_idx % MOVES_PER_BYTE) * 2)), None);
+        }
+        Ok(replay)
+    }
+
+    /// Plays the game through again, and checks that it really does end with the claimed score and largest tile
+    pub fn verify(&self, claim: &ClaimedResult) -> Result<(), ReplayError> {
+        let board = self.play()?;
+        let actual = ClaimedResult { score: board.score(), max_tile: board.max_tile() };
+        if actual != *claim {
+            return Err(ReplayError::ClaimMismatch { claimed: *claim, actual });
+        }
+        Ok(())
+    }
+}
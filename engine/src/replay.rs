@@ -0,0 +1,159 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, ClaimedResult, Direction, MoveOutcome, Rules};
+
+/// Bumped whenever the layout of a replay changes, so that older replays are rejected rather than misread
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// One press that changed the board
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct ReplayMove {
+    pub direction: Direction,
+    /// Milliseconds since the start of the game, if the recorder kept track
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_ms: Option<u64>,
+}
+
+/// How the game stood when the replay was written, so that a re-run can be checked against it
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ReplayOutcome {
+    pub score: usize,
+    /// The tiles on the board, in the one-line notation
+    pub tiles: String,
+}
+
+/// A whole game, captured as the rules and seed it started from plus every move the player made. Since the seed
+/// decides every spawned tile, that's all we need to play the game through again.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    version: u32,
+    pub rules: Rules,
+    pub seed: u64,
+    pub moves: Vec<ReplayMove>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<ReplayOutcome>,
+}
+
+/// Why a replay couldn't be played through
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReplayError {
+    /// The text isn't a replay at all
+    Malformed(String),
+    /// The replay was written in a format we no longer (or don't yet) understand
+    UnsupportedVersion(u32),
+    /// The rules describe a game that can't be played
+    InvalidRules,
+    /// Only moves that change the board are recorded, so this one not doing anything means the re-run has gone
+    /// differently to the original game
+    MoveDidNothing { move_idx: usize, direction: Direction },
+    /// Every move went through, but the game ended up somewhere other than where the recording says it did
+    OutcomeMismatch { expected: ReplayOutcome, actual: ReplayOutcome },
+    /// The game doesn't end with the result that was claimed for it
+    ClaimMismatch { claimed: ClaimedResult, actual: ClaimedResult },
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(message) => write!(f, "Not a replay: {message}"),
+            Self::UnsupportedVersion(version) => write!(f, "Unsupported replay format version: {version}"),
+            Self::InvalidRules => write!(f, "The replay's rules aren't playable"),
+            Self::MoveDidNothing { move_idx, direction } => {
+                write!(f, "Move {} ({direction:?}) didn't change the board", move_idx + 1)
+            }
+            Self::OutcomeMismatch { expected, actual } => write!(
+                f,
+                "Expected to finish on {} with {} points, but finished on {} with {} points",
+                expected.tiles, expected.score, actual.tiles, actual.score,
+            ),
+            Self::ClaimMismatch { claimed, actual } => write!(f, "Claimed {claimed}, but the game ended on {actual}"),
+        }
+    }
+}
+
+impl ReplayOutcome {
+    fn of(board: &Board) -> Self {
+        Self {
+            score: board.score(),
+            tiles: board.to_notation(),
+        }
+    }
+}
+
+impl Replay {
+    pub fn new(seed: u64, rules: Rules) -> Self {
+        Self {
+            version: REPLAY_FORMAT_VERSION,
+            rules,
+            seed,
+            moves: vec![],
+            outcome: None,
+        }
+    }
+
+    /// Starts a replay of the game on this board, which must have only just begun
+    pub fn for_board(board: &Board) -> Self {
+        Self::new(board.seed(), board.rules().clone())
+    }
+
+    pub fn record(&mut self, direction: Direction, timestamp_ms: Option<u64>) {
+        self.moves.push(ReplayMove { direction, timestamp_ms });
+    }
+
+    /// Notes where the game has got to, so that re-runs can check they end up in the same place
+    pub fn record_outcome(&mut self, board: &Board) {
+        self.outcome = Some(ReplayOutcome::of(board));
+    }
+
+    /// The board as it was before the first move. Fails if the rules describe a board that can't be built.
+    pub fn starting_board(&self) -> Result<Board, ReplayError> {
+        if !self.rules.is_playable() {
+            return Err(ReplayError::InvalidRules);
+        }
+        let mut board = Board::with_seed_and_rules(self.seed, self.rules.clone());
+        board.start_new_game(Some(self.seed));
+        Ok(board)
+    }
+
+    /// Plays the game through again, handing each move to `observe` along with the board just after it
+    pub fn play_with(&self, mut observe: impl FnMut(&ReplayMove, &MoveOutcome, &Board)) -> Result<Board, ReplayError> {
+        let mut board = self.starting_board()?;
+        for (move_idx, replay_move) in self.moves.iter().enumerate() {
+            let outcome = board.press(replay_move.direction);
+            if !outcome.board_changed() {
+                return Err(ReplayError::MoveDidNothing { move_idx, direction: replay_move.direction });
+            }
+            board.spawn_tiles_for_turn();
+            observe(replay_move, &outcome, &board);
+        }
+        if let Some(expected) = &self.outcome {
+            let actual = ReplayOutcome::of(&board);
+            if *expected != actual {
+                return Err(ReplayError::OutcomeMismatch { expected: expected.clone(), actual });
+            }
+        }
+        Ok(board)
+    }
+
+    /// Plays the game through again, returning the board as it stands after the last move
+    pub fn play(&self) -> Result<Board, ReplayError> {
+        self.play_with(|_, _, _| {})
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ReplayError> {
+        let replay: Self = serde_json::from_str(json).map_err(|e| ReplayError::Malformed(e.to_string()))?;
+        if replay.version != REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(replay.version));
+        }
+        if !replay.rules.is_playable() {
+            return Err(ReplayError::InvalidRules);
+        }
+        Ok(replay)
+    }
+}
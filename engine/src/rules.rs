@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{DEFAULT_BOARD_SIZE, DEFAULT_GOAL_TILE};
+
+/// The most cells a playable board may have. This is far beyond any board a person would play on, and stops rules
+/// read back from a file from asking for a board too large to allocate.
+pub const MAX_BOARD_CELLS: usize = 1 << 16;
+
+/// A value that a newly spawned tile can take, along with how likely it is relative to the other entries
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct SpawnWeight {
+    pub value: usize,
+    pub weight: u32,
+}
+
+/// The knobs that distinguish one variant of the game from another
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Rules {
+    /// The number of columns on the board
+    pub width: usize,
+    /// The number of rows on the board
+    pub height: usize,
+    /// Creating a tile of this value wins the game
+    pub goal_tile: usize,
+    /// The possible values of each spawned tile. At least one entry must have a non-zero weight.
+    pub spawn_table: Vec<SpawnWeight>,
+    /// How many new tiles appear after each move that changes the board
+    pub tiles_per_turn: usize,
+}
+
+impl Rules {
+    /// The rules of the original game: a 4 spawns 10% of the time, and a 2 otherwise
+    pub fn classic() -> Self {
+        Self {
+            width: DEFAULT_BOARD_SIZE,
+            height: DEFAULT_BOARD_SIZE,
+            goal_tile: DEFAULT_GOAL_TILE,
+            spawn_table: vec![
+                SpawnWeight { value: 2, weight: 9 },
+                SpawnWeight { value: 4, weight: 1 },
+            ],
+            tiles_per_turn: 1,
+        }
+    }
+
+    /// Twos and fours are equally likely, which leaves fewer opportunities to merge
+    pub fn hard() -> Self {
+        Self {
+            spawn_table: vec![
+                SpawnWeight { value: 2, weight: 1 },
+                SpawnWeight { value: 4, weight: 1 },
+            ],
+            ..Self::classic()
+        }
+    }
+
+    /// These rules, played on a board of a different size
+    pub fn with_size(self, width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            ..self
+        }
+    }
+
+    /// Whether a game can actually be played under these rules. The presets always can, but rules read back from a
+    /// file might have been tampered with.
+    pub fn is_playable(&self) -> bool {
+        self.width > 0
+            && self.height > 0
+            && self.width.checked_mul(self.height).is_some_and(|cells| cells <= MAX_BOARD_CELLS)
+            && self.goal_tile >= 2
+            && self.goal_tile.is_power_of_two()
+            && self.spawn_table.iter().any(|spawn| spawn.weight > 0)
+            // Spawning draws from the total weight, which has to fit in a u32
+            && self.spawn_table.iter().try_fold(0u32, |total, spawn| total.checked_add(spawn.weight)).is_some()
+            && self.spawn_table.iter().all(|spawn| spawn.value >= 2 && spawn.value.is_power_of_two())
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl TryFrom<&str> for Rules {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "classic" => Ok(Self::classic()),
+            "hard" => Ok(Self::hard()),
+            // Unknown preset
+            _ => Err(()),
+        }
+    }
+}
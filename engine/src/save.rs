@@ -0,0 +1,135 @@
+use std::fmt::{Display, Formatter};
+
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, BoardCoordinate, CellContents, Rules};
+
+/// Bumped whenever the layout of a saved game changes, so that older saves are rejected rather than misread
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+
+/// Why a saved game couldn't be loaded
+#[derive(Debug, PartialEq, Clone)]
+pub enum LoadError {
+    /// The text isn't a saved game at all
+    Malformed(String),
+    /// The game was saved in a format we no longer (or don't yet) understand
+    UnsupportedVersion(u32),
+    /// The rules describe a game that can't be played
+    InvalidRules,
+    /// The tiles don't fill out a board of the size given by the rules
+    WrongSize { width: usize, height: usize },
+    /// A tile that could never appear in a game played under the saved rules
+    InvalidTile { coords: BoardCoordinate, value: usize },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(message) => write!(f, "Not a saved game: {message}"),
+            Self::UnsupportedVersion(version) => write!(f, "Unsupported save format version: {version}"),
+            Self::InvalidRules => write!(f, "The saved rules aren't playable"),
+            Self::WrongSize { width, height } => write!(f, "The saved tiles don't make up a {width}x{height} board"),
+            Self::InvalidTile { coords, value } => write!(f, "Invalid tile {value} at {coords}"),
+        }
+    }
+}
+
+/// The layout of a saved game. The tiles are stored as a grid of values rather than as `Cell`s, so that the file
+/// stays readable (and editable) by hand.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SavedGame {
+    version: u32,
+    rules: Rules,
+    /// Row by row from the top-left, with 0 standing in for an empty cell
+    tiles: Vec<Vec<usize>>,
+    score: usize,
+    best_score: usize,
+    move_count: usize,
+    reached_goal: bool,
+    seed: u64,
+    /// Where the generator has got to, so that the loaded game spawns the same tiles the original would have
+    rng: ChaCha8Rng,
+}
+
+impl From<Board> for SavedGame {
+    fn from(board: Board) -> Self {
+        let tiles = board.cells.chunks(board.width()).map(|row| {
+            row.iter().map(|cell| match cell.contents {
+                CellContents::Empty => 0,
+                CellContents::Occupied(value) => value,
+            }).collect()
+        }).collect();
+        Self {
+            version: SAVE_FORMAT_VERSION,
+            rules: board.rules,
+            tiles,
+            score: board.score,
+            best_score: board.best_score,
+            move_count: board.move_count,
+            reached_goal: board.reached_goal,
+            seed: board.seed,
+            rng: board.rng,
+        }
+    }
+}
+
+impl TryFrom<SavedGame> for Board {
+    type Error = LoadError;
+
+    fn try_from(saved: SavedGame) -> Result<Self, Self::Error> {
+        if saved.version != SAVE_FORMAT_VERSION {
+            return Err(LoadError::UnsupportedVersion(saved.version));
+        }
+        let rules = saved.rules;
+        if !rules.is_playable() {
+            return Err(LoadError::InvalidRules);
+        }
+        if saved.tiles.len() != rules.height || saved.tiles.iter().any(|row| row.len() != rules.width) {
+            return Err(LoadError::WrongSize { width: rules.width, height: rules.height });
+        }
+
+        // Nothing smaller than the smallest spawn can ever appear. At the other end, each doubling of the largest
+        // spawn needs one more cell to build up the pair that merges into it, which bounds the largest tile by the
+        // size of the board.
+        let spawn_exponents = rules.spawn_table.iter()
+            .filter(|spawn| spawn.weight > 0)
+            .map(|spawn| spawn.value.trailing_zeros() as usize);
+        let min_exponent = spawn_exponents.clone().min().unwrap();
+        let max_exponent = spawn_exponents.max().unwrap() + (rules.width * rules.height) - 1;
+
+        let mut board = Board::with_seed_and_rules(saved.seed, rules);
+        for (row_idx, row) in saved.tiles.iter().enumerate() {
+            for (col_idx, value) in row.iter().enumerate() {
+                let coords = BoardCoordinate(col_idx, row_idx);
+                let contents = match *value {
+                    0 => CellContents::Empty,
+                    value if value.is_power_of_two()
+                        && (min_exponent..=max_exponent).contains(&(value.trailing_zeros() as usize)) => {
+                        CellContents::Occupied(value)
+                    }
+                    value => return Err(LoadError::InvalidTile { coords, value }),
+                };
+                board.set_contents_at(coords, contents);
+            }
+        }
+        board.score = saved.score;
+        board.best_score = saved.best_score.max(saved.score);
+        board.move_count = saved.move_count;
+        board.reached_goal = saved.reached_goal;
+        board.rng = saved.rng;
+        Ok(board)
+    }
+}
+
+impl Board {
+    /// Captures everything needed to pick this game back up later, including where the tile generator has got to
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, LoadError> {
+        let saved: SavedGame = serde_json::from_str(json).map_err(|e| LoadError::Malformed(e.to_string()))?;
+        Self::try_from(saved)
+    }
+}
@@ -0,0 +1,8 @@
+use crate::{Board, Direction};
+
+/// Something that can play the game by itself, one move at a time
+pub trait Strategy {
+    /// Picks the next move to make. This is only meant to be asked while the board has a legal move left: if it
+    /// doesn't, any direction may come back.
+    fn choose(&mut self, board: &Board) -> Direction;
+}
@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+use crate::BoardCoordinate;
+
+/// Identifies a tile for as long as it stays on the board. A tile keeps its id as it slides around, and when two
+/// tiles merge the combined tile keeps the id of the one it merged into.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize)]
+pub struct TileId(pub u64);
+
+/// A tile currently on the board
+#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+pub struct Tile {
+    pub id: TileId,
+    pub coords: BoardCoordinate,
+    pub value: usize,
+}
+
+/// Something that happened to a tile, in enough detail for a renderer to animate it
+#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+#[serde(tag = "kind")]
+pub enum TileEvent {
+    /// The tile slid to a new cell. It may also be the target of a merge in the same move.
+    Moved { id: TileId, from: BoardCoordinate, to: BoardCoordinate },
+    /// The tile slid into another tile and was absorbed by it, leaving `into` holding `value`
+    Merged { id: TileId, into: TileId, from: BoardCoordinate, to: BoardCoordinate, value: usize },
+    /// A new tile appeared
+    Spawned { id: TileId, at: BoardCoordinate, value: usize },
+    /// The tile was cleared away as the board was emptied for a new game
+    Removed { id: TileId, at: BoardCoordinate },
+}
@@ -0,0 +1,131 @@
+//! Reads boards back out of the framed text produced by `Display for Board`, so that games captured in CLI logs and
+//! blog transcripts can be picked up again.
+//!
+//! The renderings have changed shape over time (the native program starts with a blank line and a score line, while
+//! the earlier browser builds had neither, nor any trailing newlines), and text copied out of a terminal or a web
+//! page tends to pick up stray indentation. Rather than relying on exact widths, the parser just looks for the
+//! horizontal trim lines that separate rows, and the `|` characters that separate columns.
+use std::iter::Peekable;
+
+use crate::{Board, BoardCoordinate, CellContents, Rules};
+
+fn is_trim_line(line: &str) -> bool {
+    line.len() > 1 && line.chars().all(|c| c == '-')
+}
+
+fn is_cell_line(line: &str) -> bool {
+    line.len() > 1 && line.starts_with('|') && line.ends_with('|')
+}
+
+/// The text between each pair of `|`s
+fn cell_texts(line: &str) -> Vec<&str> {
+    line[1..line.len() - 1].split('|').map(str::trim).collect()
+}
+
+/// Picks the score and best score out of a line like `Score: 12    Best: 40`
+fn parse_score_line(line: &str) -> Option<(usize, usize)> {
+    let mut words = line.split_whitespace();
+    if words.next()? != "Score:" {
+        return None;
+    }
+    let score = words.next()?.parse().ok()?;
+    let best_score = match (words.next(), words.next()) {
+        (Some("Best:"), Some(best_score)) => best_score.parse().ok()?,
+        // Older renderings didn't show the best score
+        _ => score,
+    };
+    Some((score, best_score))
+}
+
+fn parse_tile(text: &str) -> Result<CellContents, ()> {
+    let value: usize = text.parse().map_err(|_| ())?;
+    if value < 2 || !value.is_power_of_two() {
+        // Not a tile that can appear in the game
+        return Err(());
+    }
+    Ok(CellContents::Occupied(value))
+}
+
+/// Reads the cells of one row of the board: every line between two trim lines. A tile is written on just one of
+/// these lines, and the rest are padding.
+fn parse_row(lines: &[&str]) -> Result<Vec<CellContents>, ()> {
+    let lines = lines.iter().map(|line| cell_texts(line)).collect::<Vec<_>>();
+    let width = lines[0].len();
+    if lines.iter().any(|texts| texts.len() != width) {
+        return Err(());
+    }
+    (0..width).map(|col_idx| {
+        let mut texts = lines.iter().map(|texts| texts[col_idx]).filter(|text| !text.is_empty());
+        match (texts.next(), texts.next()) {
+            (None, _) => Ok(CellContents::Empty),
+            (Some(text), None) => parse_tile(text),
+            // Two different things written in the same cell
+            (Some(_), Some(_)) => Err(()),
+        }
+    }).collect()
+}
+
+/// Skips ahead to the next rendered board and parses it, or returns `None` if there aren't any more
+fn parse_next_board<'a>(lines: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Result<Board, ()>> {
+    // The score is shown just above the board, if at all
+    let mut score = None;
+    loop {
+        let line = lines.next()?;
+        if is_trim_line(line) {
+            break;
+        }
+        if let Some(parsed_score) = parse_score_line(line) {
+            score = Some(parsed_score);
+        }
+    }
+
+    let mut rows = vec![];
+    let mut lines_in_row = vec![];
+    while let Some(line) = lines.next_if(|line| is_trim_line(line) || is_cell_line(line)) {
+        if !is_trim_line(line) {
+            lines_in_row.push(line);
+            continue;
+        }
+        if lines_in_row.is_empty() {
+            // Two trim lines in a row
+            return Some(Err(()));
+        }
+        match parse_row(&lines_in_row) {
+            Ok(row) => rows.push(row),
+            Err(()) => return Some(Err(())),
+        }
+        lines_in_row.clear();
+    }
+    if rows.is_empty() || !lines_in_row.is_empty() || rows.iter().any(|row| row.len() != rows[0].len()) {
+        // The board was cut off, or its rows don't line up
+        return Some(Err(()));
+    }
+
+    let mut board = Board::with_seed_and_rules(0, Rules::classic().with_size(rows[0].len(), rows.len()));
+    for (row_idx, row) in rows.iter().enumerate() {
+        for (col_idx, contents) in row.iter().enumerate() {
+            board.set_contents_at(BoardCoordinate(col_idx, row_idx), *contents);
+        }
+    }
+    if let Some((score, best_score)) = score {
+        board.score = score;
+        board.best_score = best_score.max(score);
+    }
+    Some(Ok(board))
+}
+
+impl Board {
+    /// Reads the first board rendered in the text, ignoring anything around it. As with the one-line notation, the
+    /// rules are the classic ones (at whatever size the rendering shows) and the board starts from seed 0.
+    /// Returns `None` if there's no board in the text, or if it's garbled.
+    pub fn from_rendering(text: &str) -> Option<Self> {
+        parse_next_board(&mut text.lines().map(str::trim).peekable())?.ok()
+    }
+
+    /// Reads every board rendered in a transcript of a game, in the order they appear. Returns `None` if any of
+    /// them is garbled.
+    pub fn all_from_rendering(text: &str) -> Option<Vec<Self>> {
+        let mut lines = text.lines().map(str::trim).peekable();
+        std::iter::from_fn(|| parse_next_board(&mut lines)).collect::<Result<_, _>>().ok()
+    }
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{board_from_rows, play_out, start_game};
+use engine::{CornerPlayer, CyclicPlayer, Direction, GreedyPlayer, RandomPlayer, Strategy};
+
+#[test]
+fn every_player_finishes_a_game_with_legal_moves() {
+    play_out(&mut start_game(1), &mut RandomPlayer::with_seed(1));
+    play_out(&mut start_game(1), &mut CornerPlayer);
+    play_out(&mut start_game(1), &mut GreedyPlayer);
+    play_out(&mut start_game(1), &mut CyclicPlayer::default());
+}
+
+#[test]
+fn random_player_is_repeatable_under_a_seed() {
+    let first = play_out(&mut start_game(2), &mut RandomPlayer::with_seed(3));
+    assert_eq!(play_out(&mut start_game(2), &mut RandomPlayer::with_seed(3)), first);
+    assert_ne!(play_out(&mut start_game(2), &mut RandomPlayer::with_seed(4)), first);
+}
+
+#[test]
+fn corner_player_prefers_down_then_left() {
+    let board = board_from_rows([
+        [2, 0, 0, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 4],
+    ]);
+    assert_eq!(CornerPlayer.choose(&board), Direction::Down);
+    let bottom_row_only = board_from_rows([
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [0, 2, 0, 4],
+    ]);
+    assert_eq!(CornerPlayer.choose(&bottom_row_only), Direction::Left);
+    let packed_into_the_corner = board_from_rows([
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [4, 0, 0, 0],
+        [2, 8, 0, 0],
+    ]);
+    assert_eq!(CornerPlayer.choose(&packed_into_the_corner), Direction::Right);
+}
+
+#[test]
+fn greedy_player_takes_the_biggest_merge() {
+    let board = board_from_rows([
+        [2, 2, 0, 0],
+        [0, 0, 0, 0],
+        [16, 0, 0, 0],
+        [16, 0, 0, 0],
+    ]);
+    // Merging the 2s is worth 4 points, and merging the 16s is worth 32
+    assert!(matches!(GreedyPlayer.choose(&board), Direction::Up | Direction::Down));
+}
+
+#[test]
+fn cyclic_player_goes_round_in_order_skipping_blocked_moves() {
+    let mut player = CyclicPlayer::default();
+    let open_board = board_from_rows([
+        [0, 0, 0, 0],
+        [0, 2, 0, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+    ]);
+    let chosen = (0..5).map(|_| player.choose(&open_board)).collect::<Vec<_>>();
+    assert_eq!(chosen, vec![Direction::Left, Direction::Down, Direction::Right, Direction::Up, Direction::Left]);
+
+    // Down is next, but the only tile is already at the bottom
+    let at_the_bottom = board_from_rows([
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [0, 2, 0, 0],
+    ]);
+    assert_eq!(player.choose(&at_the_bottom), Direction::Right);
+    assert_eq!(player.choose(&at_the_bottom), Direction::Up);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::board_from_rows;
+use engine::{Bitboard, Board, BoardCoordinate, CellContents, Direction, Rules};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+fn random_board(rng: &mut ChaCha8Rng) -> Board {
+    let mut board = Board::new();
+    for cell in board.cells.iter_mut() {
+        if rng.gen_bool(0.75) {
+            cell.contents = CellContents::Occupied(1 << rng.gen_range(1..=6));
+        }
+    }
+    board
+}
+
+fn contents(board: &Board) -> Vec<CellContents> {
+    board.cells.iter().map(|cell| cell.contents).collect()
+}
+
+#[test]
+fn round_trip_is_lossless() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    for _ in 0..200 {
+        let board = random_board(&mut rng);
+        let bitboard = Bitboard::try_from(&board).unwrap();
+        assert_eq!(contents(&Board::from(bitboard)), contents(&board));
+    }
+}
+
+#[test]
+fn layout() {
+    let board = board_from_rows([
+        [2, 0, 0, 4],
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [8, 0, 0, 32768],
+    ]);
+    let bitboard = Bitboard::try_from(&board).unwrap();
+    assert_eq!(bitboard, Bitboard(0xf003_0000_0000_2001));
+    assert_eq!(bitboard.exponent_at(BoardCoordinate(3, 0)), 2);
+    assert_eq!(bitboard.max_exponent(), 15);
+    assert_eq!(bitboard.empty_cell_count(), 12);
+}
+
+#[test]
+fn rejects_boards_that_cannot_be_packed() {
+    assert!(Bitboard::try_from(&Board::with_rules(Rules::default().with_size(5, 5))).is_err());
+    assert!(Bitboard::try_from(&board_from_rows([[3, 0, 0, 0], [0; 4], [0; 4], [0; 4]])).is_err());
+    assert!(Bitboard::try_from(&board_from_rows([[1, 0, 0, 0], [0; 4], [0; 4], [0; 4]])).is_err());
+    assert!(Bitboard::try_from(&board_from_rows([[65536, 0, 0, 0], [0; 4], [0; 4], [0; 4]])).is_err());
+}
+
+#[test]
+fn transpose_is_an_involution() {
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    for _ in 0..200 {
+        let bitboard = Bitboard(rng.gen());
+        assert_eq!(bitboard.transpose().transpose(), bitboard);
+        assert_eq!(
+            bitboard.transpose().exponent_at(BoardCoordinate(1, 3)),
+            bitboard.exponent_at(BoardCoordinate(3, 1)),
+        );
+    }
+}
+
+#[test]
+fn press_matches_board() {
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    for _ in 0..500 {
+        let board = random_board(&mut rng);
+        let bitboard = Bitboard::try_from(&board).unwrap();
+        for direction in Direction::ALL {
+            let mut pressed = board.clone();
+            let outcome = pressed.press(direction);
+            let (pressed_bitboard, score) = bitboard.press(direction);
+            assert_eq!(pressed_bitboard, Bitboard::try_from(&pressed).unwrap(), "Pressing {direction:?} on {board}");
+            assert_eq!(score, outcome.score_delta);
+        }
+        assert_eq!(bitboard.legal_moves(), board.legal_moves());
+    }
+}
+
+#[test]
+fn largest_tiles_do_not_overflow() {
+    let board = board_from_rows([[32768, 32768, 0, 0], [0; 4], [0; 4], [0; 4]]);
+    let (pressed, score) = Bitboard::try_from(&board).unwrap().press(Direction::Left);
+    assert_eq!(pressed, Bitboard::try_from(&board).unwrap());
+    assert_eq!(score, 0);
+}
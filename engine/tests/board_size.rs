@@ -0,0 +1,71 @@
+mod common;
+
+use common::{lane_count, read_lane, reference_reduce};
+use engine::{Board, Direction, Rules};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+const SIZES: [(usize, usize); 6] = [(3, 3), (5, 5), (6, 6), (5, 3), (3, 6), (1, 4)];
+
+fn random_board(width: usize, height: usize, rng: &mut ChaCha8Rng) -> Board {
+    let mut board = Board::with_rules(Rules::default().with_size(width, height));
+    for cell in board.cells.iter_mut() {
+        if rng.gen_bool(0.7) {
+            cell.contents = engine::CellContents::Occupied(*[2, 4, 8].choose(rng).unwrap());
+        }
+    }
+    board
+}
+
+#[test]
+fn every_lane_follows_the_classic_rule_on_any_size() {
+    let mut rng = ChaCha8Rng::seed_from_u64(21);
+    for (width, height) in SIZES {
+        for _ in 0..50 {
+            let board = random_board(width, height, &mut rng);
+            for direction in Direction::ALL {
+                let mut pressed = board.clone();
+                pressed.press(direction);
+                for lane_idx in 0..lane_count(&board, direction) {
+                    assert_eq!(
+                        read_lane(&pressed, direction, lane_idx),
+                        reference_reduce(&read_lane(&board, direction, lane_idx)),
+                        "Pressing {direction:?} on lane {lane_idx} of a {width}x{height} board",
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn spawning_fills_every_cell() {
+    for (width, height) in SIZES {
+        let mut board = Board::with_seed_and_rules(4, Rules::default().with_size(width, height));
+        for _ in 0..(width * height) {
+            assert!(board.spawn_tile_in_random_location().is_some());
+        }
+        assert!(board.is_full());
+        assert_eq!(board.spawn_tile_in_random_location(), None);
+    }
+}
+
+#[test]
+fn display_matches_dimensions() {
+    let board = Board::with_rules(Rules::default().with_size(5, 3));
+    let rendering = board.to_string();
+    let grid_lines = rendering.lines().skip_while(|line| !line.starts_with('-')).collect::<Vec<_>>();
+    // Each of the 3 rows takes a trim line above it plus 3 lines of cells, and there is one more trim line below
+    assert_eq!(grid_lines.len(), 1 + (3 * 4));
+    assert!(grid_lines.iter().all(|line| line.len() == (5 * 11) + 1));
+}
+
+#[test]
+fn reset_with_rules_resizes() {
+    let mut board = Board::new();
+    board.spawn_tile_in_random_location();
+    board.reset_with_rules(Rules::default().with_size(6, 2));
+    assert_eq!((board.width(), board.height()), (6, 2));
+    assert_eq!(board.cells.len(), 12);
+    assert!(board.cells.iter().all(|cell| cell.is_empty()));
+}
@@ -0,0 +1,129 @@
+//! Helpers shared between the integration tests
+#![allow(dead_code)]
+
+use engine::{Board, BoardCoordinate, CellContents, Direction, MoveOutcome, Replay, Rules, Strategy};
+
+pub fn contents_from_value(value: usize) -> CellContents {
+    match value {
+        0 => CellContents::Empty,
+        value => CellContents::Occupied(value),
+    }
+}
+
+pub fn value_from_contents(contents: CellContents) -> usize {
+    match contents {
+        CellContents::Empty => 0,
+        CellContents::Occupied(value) => value,
+    }
+}
+
+/// The coordinates of a lane, listed from the edge that tiles are pushed towards when pressing `direction`
+pub fn lane_coords(board: &Board, direction: Direction, lane_idx: usize) -> Vec<BoardCoordinate> {
+    let (width, height) = (board.width(), board.height());
+    match direction {
+        Direction::Left => (0..width).map(|x| BoardCoordinate(x, lane_idx)).collect(),
+        Direction::Right => (0..width).rev().map(|x| BoardCoordinate(x, lane_idx)).collect(),
+        Direction::Up => (0..height).map(|y| BoardCoordinate(lane_idx, y)).collect(),
+        Direction::Down => (0..height).rev().map(|y| BoardCoordinate(lane_idx, y)).collect(),
+    }
+}
+
+pub fn lane_count(board: &Board, direction: Direction) -> usize {
+    match direction {
+        Direction::Left | Direction::Right => board.height(),
+        Direction::Up | Direction::Down => board.width(),
+    }
+}
+
+pub fn write_lane(board: &mut Board, direction: Direction, lane_idx: usize, lane: &[usize]) {
+    for (coords, value) in lane_coords(board, direction, lane_idx).into_iter().zip(lane.iter()) {
+        board.set_contents_at(coords, contents_from_value(*value));
+    }
+}
+
+pub fn read_lane(board: &Board, direction: Direction, lane_idx: usize) -> Vec<usize> {
+    lane_coords(board, direction, lane_idx).into_iter().map(|coords| {
+        value_from_contents(board.contents_at(coords))
+    }).collect()
+}
+
+/// Fills the board from a list of rows, where `0` denotes an empty cell
+pub fn board_from_rows<const W: usize, const H: usize>(rows: [[usize; W]; H]) -> Board {
+    let mut board = Board::with_rules(engine::Rules::default().with_size(W, H));
+    for (row_idx, row) in rows.iter().enumerate() {
+        for (col_idx, value) in row.iter().enumerate() {
+            board.set_contents_at(BoardCoordinate(col_idx, row_idx), contents_from_value(*value));
+        }
+    }
+    board
+}
+
+/// Straightforward model of the classic rule: compact the lane, then merge each pair from the leading edge at most once
+pub fn reference_reduce(lane: &[usize]) -> Vec<usize> {
+    let tiles = lane.iter().copied().filter(|value| *value != 0).collect::<Vec<_>>();
+    let mut out = vec![];
+    let mut cursor = 0;
+    while cursor < tiles.len() {
+        if cursor + 1 < tiles.len() && tiles[cursor] == tiles[cursor + 1] {
+            out.push(tiles[cursor] * 2);
+            cursor += 2;
+        } else {
+            out.push(tiles[cursor]);
+            cursor += 1;
+        }
+    }
+    out.resize(lane.len(), 0);
+    out
+}
+
+/// A board at the start of a game under the given rules, with its starting tiles already spawned
+pub fn start_game_with_rules(seed: u64, rules: Rules) -> Board {
+    let mut board = Board::with_seed_and_rules(seed, rules);
+    board.start_new_game(Some(seed));
+    board
+}
+
+pub fn start_game(seed: u64) -> Board {
+    start_game_with_rules(seed, Rules::default())
+}
+
+/// Lets the player make up to `max_moves` moves, checking that every move it picks is legal, and returns the moves
+/// it chose
+pub fn play_up_to(board: &mut Board, player: &mut impl Strategy, max_moves: usize) -> Vec<Direction> {
+    let mut directions = vec![];
+    while board.has_any_legal_move() && directions.len() < max_moves {
+        let direction = player.choose(board);
+        assert!(board.press(direction).board_changed(), "{direction:?} is not a legal move on\n{board}");
+        board.spawn_tiles_for_turn();
+        directions.push(direction);
+    }
+    directions
+}
+
+/// Lets the player finish the game, checking that every move it picks is legal, and returns the moves it chose
+pub fn play_out(board: &mut Board, player: &mut impl Strategy) -> Vec<Direction> {
+    play_up_to(board, player, usize::MAX)
+}
+
+/// Starts a game and lets the player make up to `max_moves` moves, recording them the way the front-ends do
+pub fn record_game(seed: u64, rules: Rules, player: &mut impl Strategy, max_moves: usize) -> (Replay, Board) {
+    let mut board = start_game_with_rules(seed, rules);
+    let mut replay = Replay::for_board(&board);
+    for direction in play_up_to(&mut board, player, max_moves) {
+        replay.record(direction, None);
+    }
+    (replay, board)
+}
+
+/// Presses Left, Down, Right and Up in turn, `presses` times in all, spawning tiles after every press that changes
+/// the board. `after_press` is handed the board after each press (and any spawn), along with what the press did.
+pub fn cycle_through_directions(board: &mut Board, presses: usize, mut after_press: impl FnMut(&Board, &MoveOutcome)) {
+    let directions = [Direction::Left, Direction::Down, Direction::Right, Direction::Up];
+    for direction in directions.into_iter().cycle().take(presses) {
+        let outcome = board.press(direction);
+        if outcome.board_changed() {
+            board.spawn_tiles_for_turn();
+        }
+        after_press(board, &outcome);
+    }
+}
@@ -0,0 +1,84 @@
+use engine::{board, Board, Evaluation, EvaluationWeights};
+
+fn evaluate(board: &Board) -> Evaluation {
+    Evaluation::of(board)
+}
+
+#[test]
+fn counts_empty_cells() {
+    assert_eq!(evaluate(&board!("..../..../..../....")).empty_cells, 16.0);
+    assert_eq!(evaluate(&board!("1.../..2./..../...3")).empty_cells, 13.0);
+    assert_eq!(evaluate(&board!("1212/2121/1212/2121")).empty_cells, 0.0);
+}
+
+#[test]
+fn monotonic_rows_and_columns_carry_no_penalty() {
+    // Every row and column runs steadily in one direction or the other
+    let ordered = board!("4321/3210/2100/1000");
+    assert_eq!(evaluate(&ordered).monotonicity, 0.0);
+    // The middle of the top row dips by 2 and recovers by 2, and the same goes for the left column
+    let dipping = board!("3133/1.../3.../3...");
+    assert_eq!(evaluate(&dipping).monotonicity, -4.0);
+}
+
+#[test]
+fn smoothness_measures_the_steps_between_neighbours() {
+    assert_eq!(evaluate(&board!("1111/..../..../....")).smoothness, 0.0);
+    // Gaps are skipped over: the 2 and the 16 sit next to each other, three merges apart
+    assert_eq!(evaluate(&board!("1..4/..../..../....")).smoothness, -3.0);
+    assert_eq!(evaluate(&board!("13../..../..../....")).smoothness, -2.0);
+}
+
+#[test]
+fn spots_the_largest_tile_in_a_corner() {
+    assert_eq!(evaluate(&board!("..../..../..../...b")).max_tile_in_corner, 1.0);
+    assert_eq!(evaluate(&board!("..../.b../..../...1")).max_tile_in_corner, 0.0);
+    // Any corner will do, on any size of board
+    assert_eq!(evaluate(&board!("..5/.../1..")).max_tile_in_corner, 1.0);
+    assert_eq!(evaluate(&board!("..../..../..../....")).max_tile_in_corner, 0.0);
+}
+
+#[test]
+fn counts_pairs_that_could_merge() {
+    assert_eq!(evaluate(&board!("1.1./..../..../....")).merge_potential, 1.0);
+    // Three in a row holds two neighbouring pairs, and the column adds a third
+    assert_eq!(evaluate(&board!("222./2.../..../....")).merge_potential, 3.0);
+    assert_eq!(evaluate(&board!("1212/2121/1212/2121")).merge_potential, 0.0);
+}
+
+#[test]
+fn total_weighs_each_term() {
+    let evaluation = evaluate(&board!("1.1./..../..../...."));
+    let only = |weights: EvaluationWeights| evaluation.total(&weights);
+    let none = EvaluationWeights {
+        empty_cells: 0.0,
+        monotonicity: 0.0,
+        smoothness: 0.0,
+        max_tile_in_corner: 0.0,
+        merge_potential: 0.0,
+    };
+    assert_eq!(only(none), 0.0);
+    assert_eq!(only(EvaluationWeights { empty_cells: 2.0, ..none }), 28.0);
+    assert_eq!(only(EvaluationWeights { merge_potential: 5.0, ..none }), 5.0);
+    assert_eq!(only(EvaluationWeights { max_tile_in_corner: 3.0, ..none }), 3.0);
+}
+
+#[test]
+fn tidy_boards_beat_messy_ones() {
+    let tidy = board!("..../..../1.../4321");
+    let messy = board!("1.3./..../2.../.4.1");
+    let weights = EvaluationWeights::default();
+    assert!(evaluate(&tidy).total(&weights) > evaluate(&messy).total(&weights));
+}
+
+#[test]
+fn breakdown_lists_every_term_and_the_total() {
+    let evaluation = evaluate(&board!("1.1./..../..../...."));
+    let breakdown = evaluation.breakdown(&EvaluationWeights::default());
+    let lines = breakdown.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].starts_with("Empty cells"));
+    assert!(lines[4].starts_with("Merge potential"));
+    assert!(lines[5].starts_with("Total"));
+    assert!(lines[5].ends_with(&format!("{:.1}", evaluation.total(&EvaluationWeights::default()))));
+}
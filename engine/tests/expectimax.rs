@@ -0,0 +1,93 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{board_from_rows, play_out, start_game, start_game_with_rules};
+use engine::{best_move, Board, CellContents, Direction, Expectimax, Strategy};
+
+/// Whether pressing `direction` loses the game, whichever tile spawns afterwards
+fn loses_whatever_spawns(board: &Board, direction: Direction) -> bool {
+    let mut after_move = board.clone();
+    if !after_move.press(direction).board_changed() {
+        return false;
+    }
+    let free_cells = after_move.cells.iter().filter(|cell| cell.is_empty()).map(|cell| cell.coords).collect::<Vec<_>>();
+    free_cells.into_iter().all(|coords| {
+        after_move.rules().spawn_table.iter().all(|spawn| {
+            let mut after_spawn = after_move.clone();
+            after_spawn.set_contents_at(coords, CellContents::Occupied(spawn.value));
+            !after_spawn.has_any_legal_move()
+        })
+    })
+}
+
+#[test]
+fn takes_the_big_merge() {
+    let board = board_from_rows([
+        [2, 4, 8, 16],
+        [16, 8, 4, 2],
+        [2, 4, 8, 16],
+        [1024, 1024, 0, 0],
+    ]);
+    // Looking any further ahead, the merge could just as well come a move later
+    let direction = Expectimax::with_depth(1).choose(&board);
+    assert!(matches!(direction, Direction::Left | Direction::Right));
+    assert!(board.legal_moves().contains(&best_move(&board)));
+}
+
+#[test]
+fn always_picks_a_legal_move() {
+    play_out(&mut start_game(1), &mut Expectimax::with_depth(1));
+}
+
+#[test]
+fn steers_clear_of_moves_that_lose() {
+    // Play at random until the board gets crowded enough that some moves would end the game
+    let mut checked_positions = 0;
+    for seed in 0..20 {
+        let mut board = start_game(seed);
+        let mut player = Expectimax::with_depth(2);
+        let mut directions = Direction::ALL.into_iter().cycle();
+        while board.has_any_legal_move() {
+            let legal_moves = board.legal_moves();
+            let losing_moves = legal_moves.iter().filter(|direction| loses_whatever_spawns(&board, **direction)).count();
+            if losing_moves > 0 && losing_moves < legal_moves.len() {
+                let direction = player.choose(&board);
+                assert!(!loses_whatever_spawns(&board, direction), "{direction:?} loses on\n{board}");
+                checked_positions += 1;
+            }
+            let direction = directions.find(|direction| legal_moves.contains(direction)).unwrap();
+            board.press(direction);
+            board.spawn_tiles_for_turn();
+        }
+    }
+    assert!(checked_positions > 0);
+}
+
+#[test]
+fn search_is_deterministic_without_a_time_budget() {
+    let mut board = start_game(7);
+    for direction in [Direction::Left, Direction::Down, Direction::Right, Direction::Down].iter().cycle().take(30) {
+        if board.press(*direction).board_changed() {
+            board.spawn_tiles_for_turn();
+        }
+    }
+    let mut first = Expectimax::with_depth(2);
+    let mut second = Expectimax::with_depth(2);
+    assert_eq!(first.choose(&board), second.choose(&board));
+    // Reusing a player doesn't carry anything over from the last search
+    assert_eq!(first.choose(&board), second.choose(&board));
+}
+
+#[test]
+fn settles_for_a_shallower_search_when_out_of_time() {
+    let board = start_game(8);
+    let mut player = Expectimax::with_time_budget(20, Duration::ZERO);
+    assert!(board.legal_moves().contains(&player.choose(&board)));
+}
+
+#[test]
+fn searches_boards_of_any_size() {
+    let mut board = start_game_with_rules(9, engine::Rules::hard().with_size(3, 2));
+    play_out(&mut board, &mut Expectimax::with_depth(3));
+}
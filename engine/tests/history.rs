@@ -0,0 +1,96 @@
+mod common;
+
+use common::start_game;
+use engine::{Board, CellContents, Direction, History};
+
+fn contents(board: &Board) -> Vec<CellContents> {
+    board.cells.iter().map(|cell| cell.contents).collect()
+}
+
+/// Makes a move the same way the front-ends do, returning whether it changed the board
+fn take_turn(board: &mut Board, history: &mut History, direction: Direction) -> bool {
+    let board_before_move = board.clone();
+    if !board.press(direction).board_changed() {
+        return false;
+    }
+    history.record(board_before_move);
+    board.spawn_tiles_for_turn();
+    true
+}
+
+#[test]
+fn undo_restores_cells_and_score() {
+    let mut board = start_game(11);
+    let mut history = History::new();
+    let initial = contents(&board);
+    for direction in Direction::ALL.iter().cycle().take(12) {
+        take_turn(&mut board, &mut history, *direction);
+    }
+    while history.undo(&mut board) {}
+    assert_eq!(contents(&board), initial);
+    assert_eq!(board.score(), 0);
+    assert_eq!(board.move_count(), 0);
+}
+
+#[test]
+fn redo_spawns_the_same_tiles() {
+    let mut board = start_game(12);
+    let mut history = History::new();
+    let direction = board.legal_moves()[0];
+    take_turn(&mut board, &mut history, direction);
+    let after_move = contents(&board);
+
+    assert!(history.undo(&mut board));
+    assert!(history.redo(&mut board));
+    assert_eq!(contents(&board), after_move);
+
+    // Making the move again by hand also gets the same spawn, because the generator state was rewound too
+    assert!(history.undo(&mut board));
+    take_turn(&mut board, &mut history, direction);
+    assert_eq!(contents(&board), after_move);
+}
+
+#[test]
+fn new_move_discards_redo() {
+    let mut board = start_game(13);
+    let mut history = History::new();
+    let direction = board.legal_moves()[0];
+    take_turn(&mut board, &mut history, direction);
+    history.undo(&mut board);
+    assert!(history.can_redo());
+    let other_direction = *board.legal_moves().iter().find(|d| **d != direction).unwrap();
+    take_turn(&mut board, &mut history, other_direction);
+    assert!(!history.can_redo());
+    assert!(!history.redo(&mut board));
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut board = start_game(14);
+    let mut history = History::with_limit(3);
+    let mut moves_made = 0;
+    for direction in Direction::ALL.iter().cycle().take(20) {
+        if take_turn(&mut board, &mut history, *direction) {
+            moves_made += 1;
+        }
+    }
+    assert!(moves_made > 3);
+    let mut undone = 0;
+    while history.undo(&mut board) {
+        undone += 1;
+    }
+    assert_eq!(undone, 3);
+}
+
+#[test]
+fn undo_keeps_best_score() {
+    let mut board = Board::with_seed(15);
+    let mut history = History::new();
+    board.cells[0].contents = CellContents::Occupied(8);
+    board.cells[1].contents = CellContents::Occupied(8);
+    take_turn(&mut board, &mut history, Direction::Left);
+    assert_eq!(board.best_score(), 16);
+    history.undo(&mut board);
+    assert_eq!(board.score(), 0);
+    assert_eq!(board.best_score(), 16);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::board_from_rows;
+use engine::Direction;
+
+#[test]
+fn full_board_with_a_mergeable_pair_is_not_over() {
+    let board = board_from_rows([
+        [2, 4, 2, 4],
+        [4, 2, 4, 2],
+        [2, 4, 2, 4],
+        [4, 2, 8, 8],
+    ]);
+    assert!(board.is_full());
+    assert!(board.has_any_legal_move());
+    assert_eq!(board.legal_moves(), vec![Direction::Left, Direction::Right]);
+}
+
+#[test]
+fn full_board_without_pairs_is_over() {
+    let board = board_from_rows([
+        [2, 4, 2, 4],
+        [4, 2, 4, 2],
+        [2, 4, 2, 4],
+        [4, 2, 4, 2],
+    ]);
+    assert!(!board.has_any_legal_move());
+    assert!(board.legal_moves().is_empty());
+}
+
+#[test]
+fn legal_moves_excludes_blocked_directions() {
+    let board = board_from_rows([
+        [2, 0, 0, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+    ]);
+    assert_eq!(board.legal_moves(), vec![Direction::Right, Direction::Down]);
+}
+
+#[test]
+fn spawning_on_a_full_board_does_nothing() {
+    let mut board = board_from_rows([
+        [2, 4, 2, 4],
+        [4, 2, 4, 2],
+        [2, 4, 2, 4],
+        [4, 2, 4, 2],
+    ]);
+    assert_eq!(board.spawn_tile_in_random_location(), None);
+}
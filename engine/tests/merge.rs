@@ -0,0 +1,109 @@
+mod common;
+
+use common::{board_from_rows, read_lane, reference_reduce, write_lane};
+use engine::{Board, Direction};
+
+/// Lane patterns, listed from the edge that tiles are pushed towards. `0` denotes an empty cell.
+const LANE_CASES: &[([usize; 4], [usize; 4])] = &[
+    // Nothing to do
+    ([0, 0, 0, 0], [0, 0, 0, 0]),
+    ([2, 0, 0, 0], [2, 0, 0, 0]),
+    ([2, 4, 0, 0], [2, 4, 0, 0]),
+    ([2, 4, 8, 16], [2, 4, 8, 16]),
+    // Sliding without merging
+    ([0, 0, 0, 2], [2, 0, 0, 0]),
+    ([0, 2, 0, 4], [2, 4, 0, 0]),
+    ([2, 0, 4, 0], [2, 4, 0, 0]),
+    ([0, 2, 4, 8], [2, 4, 8, 0]),
+    // A single pair
+    ([2, 2, 0, 0], [4, 0, 0, 0]),
+    ([0, 0, 2, 2], [4, 0, 0, 0]),
+    ([2, 0, 0, 2], [4, 0, 0, 0]),
+    ([0, 2, 0, 2], [4, 0, 0, 0]),
+    ([4, 2, 2, 0], [4, 4, 0, 0]),
+    ([4, 8, 2, 2], [4, 8, 4, 0]),
+    ([8, 2, 2, 8], [8, 4, 8, 0]),
+    // Three of a kind: the pair nearest the leading edge merges
+    ([2, 2, 2, 0], [4, 2, 0, 0]),
+    ([0, 2, 2, 2], [4, 2, 0, 0]),
+    ([2, 0, 2, 2], [4, 2, 0, 0]),
+    ([2, 2, 0, 2], [4, 2, 0, 0]),
+    ([4, 2, 2, 2], [4, 4, 2, 0]),
+    // Two pairs
+    ([2, 2, 2, 2], [4, 4, 0, 0]),
+    ([2, 2, 4, 4], [4, 8, 0, 0]),
+    ([4, 4, 2, 2], [8, 4, 0, 0]),
+    // A merged tile must not absorb its neighbour in the same move
+    ([4, 4, 8, 0], [8, 8, 0, 0]),
+    ([2, 2, 4, 0], [4, 4, 0, 0]),
+    ([2, 2, 4, 8], [4, 4, 8, 0]),
+    ([4, 4, 8, 16], [8, 8, 16, 0]),
+    ([0, 4, 4, 8], [8, 8, 0, 0]),
+];
+
+fn board_with_lane(direction: Direction, lane_idx: usize, lane: &[usize; 4]) -> Board {
+    let mut board = Board::new();
+    write_lane(&mut board, direction, lane_idx, lane);
+    board
+}
+
+#[test]
+fn lane_cases_in_every_direction_and_lane() {
+    for (lane, expected) in LANE_CASES.iter() {
+        for direction in Direction::ALL {
+            for lane_idx in 0..4 {
+                let mut board = board_with_lane(direction, lane_idx, lane);
+                board.press(direction);
+                assert_eq!(
+                    read_lane(&board, direction, lane_idx),
+                    expected.to_vec(),
+                    "Pressing {direction:?} on lane {lane_idx} containing {lane:?}",
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn lane_cases_agree_with_reference() {
+    for (lane, expected) in LANE_CASES.iter() {
+        assert_eq!(reference_reduce(lane.as_slice()), expected.to_vec(), "Table entry for {lane:?}");
+    }
+}
+
+#[test]
+fn every_lane_pattern_matches_reference() {
+    let alphabet = [0, 2, 4, 8];
+    for pattern_idx in 0..alphabet.len().pow(4) {
+        let lane: [usize; 4] = std::array::from_fn(|i| alphabet[(pattern_idx / alphabet.len().pow(i as u32)) % alphabet.len()]);
+        for direction in Direction::ALL {
+            let mut board = board_with_lane(direction, 1, &lane);
+            board.press(direction);
+            assert_eq!(
+                read_lane(&board, direction, 1),
+                reference_reduce(&lane),
+                "Pressing {direction:?} on {lane:?}",
+            );
+        }
+    }
+}
+
+#[test]
+fn lanes_are_independent() {
+    let mut board = board_from_rows([
+        [2, 2, 2, 2],
+        [4, 4, 8, 0],
+        [0, 0, 2, 2],
+        [2, 4, 8, 16],
+    ]);
+    board.press(Direction::Left);
+    let expected: [[usize; 4]; 4] = [
+        [4, 4, 0, 0],
+        [8, 8, 0, 0],
+        [4, 0, 0, 0],
+        [2, 4, 8, 16],
+    ];
+    for (row_idx, row) in expected.iter().enumerate() {
+        assert_eq!(read_lane(&board, Direction::Left, row_idx), row.to_vec());
+    }
+}
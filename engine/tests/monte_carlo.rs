@@ -0,0 +1,60 @@
+mod common;
+
+use common::{board_from_rows, play_out, play_up_to, start_game, start_game_with_rules};
+use engine::{Direction, MonteCarlo, Rules, RolloutObjective, Strategy};
+
+#[test]
+fn same_seed_chooses_the_same_moves() {
+    let mut first = MonteCarlo::with_seed(5).with_rollouts(10, 10);
+    let mut second = MonteCarlo::with_seed(5).with_rollouts(10, 10);
+    assert_eq!(play_up_to(&mut start_game(1), &mut first, 50), play_up_to(&mut start_game(1), &mut second, 50));
+}
+
+#[test]
+fn seed_of_the_game_does_not_leak_into_the_rollouts() {
+    // Each rollout spawns its own tiles, so a player isn't just replaying the one future the game's seed holds
+    let board = start_game(2);
+    let mut player = MonteCarlo::with_seed(6).with_rollouts(1, usize::MAX);
+    let mut chosen = vec![];
+    for _ in 0..20 {
+        chosen.push(player.choose(&board));
+    }
+    chosen.dedup();
+    assert!(chosen.len() > 1);
+}
+
+#[test]
+fn takes_the_big_merge() {
+    let board = board_from_rows([
+        [2, 4, 8, 16],
+        [16, 8, 4, 2],
+        [2, 4, 8, 16],
+        [1024, 1024, 0, 0],
+    ]);
+    // Without any random moves afterwards, all that counts is what the move itself scores
+    let mut by_score = MonteCarlo::with_seed(7).with_rollouts(5, 0);
+    assert!(matches!(by_score.choose(&board), Direction::Left | Direction::Right));
+    let mut by_max_tile = MonteCarlo::with_seed(7).with_rollouts(5, 0).with_objective(RolloutObjective::MaxTile);
+    assert!(matches!(by_max_tile.choose(&board), Direction::Left | Direction::Right));
+}
+
+#[test]
+fn plays_a_whole_game_on_any_board() {
+    let mut board = start_game_with_rules(8, Rules::hard().with_size(5, 3));
+    play_out(&mut board, &mut MonteCarlo::with_seed(8).with_rollouts(5, 5));
+    assert!(!board.has_any_legal_move());
+}
+
+#[test]
+fn beats_cycling_through_the_directions() {
+    let mut cyclic_board = start_game(9);
+    let mut moves = Direction::ALL.into_iter().cycle();
+    while cyclic_board.has_any_legal_move() {
+        let legal_moves = cyclic_board.legal_moves();
+        cyclic_board.press(moves.find(|direction| legal_moves.contains(direction)).unwrap());
+        cyclic_board.spawn_tiles_for_turn();
+    }
+    let mut board = start_game(9);
+    play_out(&mut board, &mut MonteCarlo::with_seed(9).with_rollouts(8, 5));
+    assert!(board.score() > cyclic_board.score());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{board_from_rows, cycle_through_directions, start_game};
+use engine::{board, Board};
+
+#[test]
+fn notation_describes_the_tiles() {
+    let board = board_from_rows([
+        [2, 0, 4, 0],
+        [0, 0, 8, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 2048],
+    ]);
+    assert_eq!(board.to_notation(), "1.2./..3./..../...b");
+    assert_eq!(board!("1.2./..3./..../...b").cells, board.cells);
+}
+
+#[test]
+fn rows_can_be_given_separately() {
+    assert_eq!(board!("1.2.", "..3.", "....", "...b").cells, board!("1.2./..3./..../...b").cells);
+}
+
+#[test]
+fn notation_round_trips_through_a_game() {
+    cycle_through_directions(&mut start_game(5), 60, |board, _| {
+        let parsed = board.to_notation().parse::<Board>().unwrap();
+        assert_eq!(parsed.cells, board.cells);
+    });
+}
+
+#[test]
+fn size_comes_from_the_notation() {
+    let board = board!("1.1/.../2.2");
+    assert_eq!((board.width(), board.height()), (3, 3));
+
+    let board = board!("12345/.....");
+    assert_eq!((board.width(), board.height()), (5, 2));
+    assert_eq!(board.to_notation(), "12345/.....");
+}
+
+#[test]
+fn parsing_is_deterministic() {
+    let mut first = board!("1.../..../..../....");
+    let mut second = board!("1.../..../..../....");
+    assert_eq!(first.spawn_tile_in_random_location(), second.spawn_tile_in_random_location());
+}
+
+#[test]
+fn rejects_bad_notation() {
+    // Ragged rows
+    assert!("1.2./..3/..../....".parse::<Board>().is_err());
+    // No cells at all
+    assert!("".parse::<Board>().is_err());
+    assert!("//".parse::<Board>().is_err());
+    // Not an exponent
+    assert!("1.2./..3./..../..-.".parse::<Board>().is_err());
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{play_out, start_game_with_rules};
+use engine::{
+    board, Bitboard, Board, NTupleNetwork, NTuplePlayer, Rules, WeightsError, DEFAULT_LEARNING_RATE,
+    NTUPLE_FORMAT_VERSION,
+};
+
+/// Trains a fresh network on `games` games, each drawn from `seed` the way the `train` binary does
+fn train(seed: u64, games: usize) -> NTupleNetwork {
+    let mut network = NTupleNetwork::default();
+    let mut board = Board::with_seed(seed);
+    for _ in 0..games {
+        board.start_new_game(None);
+        network.train_on_game(&mut board, DEFAULT_LEARNING_RATE);
+    }
+    network
+}
+
+fn bitboard(board: &Board) -> Bitboard {
+    Bitboard::try_from(board).unwrap()
+}
+
+#[test]
+fn fresh_network_values_everything_at_zero() {
+    let network = NTupleNetwork::default();
+    assert_eq!(network.value(bitboard(&board!("1.2./..../..3./...b"))), 0.0);
+    assert_eq!(network.games_trained, 0);
+}
+
+#[test]
+fn training_plays_whole_games_and_learns_from_them() {
+    let network = train(1, 20);
+    assert_eq!(network.games_trained, 20);
+    // An open board early on has plenty of points still to come
+    assert!(network.value(bitboard(&board!("1.../..../..../...."))) > 0.0);
+}
+
+#[test]
+fn training_is_repeatable_under_a_seed() {
+    assert_eq!(train(2, 5).to_bytes(), train(2, 5).to_bytes());
+    assert_ne!(train(2, 5).to_bytes(), train(3, 5).to_bytes());
+}
+
+#[test]
+fn symmetric_positions_are_valued_the_same() {
+    let network = train(4, 20);
+    let corner = network.value(bitboard(&board!("3.../1.../..../....")));
+    let mirrored = network.value(bitboard(&board!("...3/...1/..../....")));
+    let flipped = network.value(bitboard(&board!("..../..../1.../3...")));
+    let transposed = network.value(bitboard(&board!("31../..../..../....")));
+    for value in [mirrored, flipped, transposed] {
+        assert!((value - corner).abs() < 1e-3, "{value} != {corner}");
+    }
+}
+
+#[test]
+fn weights_round_trip() {
+    let network = train(5, 10);
+    let bytes = network.to_bytes();
+    assert_eq!(&bytes[..4], b"NTUP");
+    assert_eq!(bytes[4], NTUPLE_FORMAT_VERSION);
+    assert_eq!(NTupleNetwork::from_bytes(&bytes), Ok(network));
+}
+
+#[test]
+fn rejects_malformed_weight_files() {
+    let bytes = NTupleNetwork::default().to_bytes();
+    assert!(matches!(NTupleNetwork::from_bytes(&bytes[..bytes.len() - 1]), Err(WeightsError::Malformed(_))));
+    assert!(matches!(NTupleNetwork::from_bytes(&[bytes.as_slice(), &[0]].concat()), Err(WeightsError::Malformed(_))));
+    assert!(matches!(NTupleNetwork::from_bytes(b"2048"), Err(WeightsError::Malformed(_))));
+
+    let mut future_version = bytes.clone();
+    future_version[4] = NTUPLE_FORMAT_VERSION + 1;
+    assert_eq!(NTupleNetwork::from_bytes(&future_version), Err(WeightsError::UnsupportedVersion(NTUPLE_FORMAT_VERSION + 1)));
+
+    // The first cell of the first tuple, moved off the board
+    let mut off_the_board = bytes.clone();
+    off_the_board[4 + 1 + 8 + 1 + 1] = 16;
+    assert!(matches!(NTupleNetwork::from_bytes(&off_the_board), Err(WeightsError::Malformed(_))));
+}
+
+#[test]
+fn player_picks_legal_moves_on_any_board() {
+    let mut player = NTuplePlayer::new(train(6, 10));
+    for rules in [Rules::classic(), Rules::classic().with_size(5, 3)] {
+        play_out(&mut start_game_with_rules(7, rules), &mut player);
+    }
+}
@@ -0,0 +1,62 @@
+use engine::{Board, BoardCoordinate, CellContents, Direction, Merge, Slide};
+
+fn board_with_top_row(row: [usize; 4]) -> Board {
+    let mut board = Board::new();
+    for (col_idx, value) in row.into_iter().enumerate() {
+        if value != 0 {
+            board.cells[col_idx].contents = CellContents::Occupied(value);
+        }
+    }
+    board
+}
+
+#[test]
+fn outcome_describes_merges_and_slides() {
+    let mut board = board_with_top_row([0, 2, 2, 4]);
+    let outcome = board.press(Direction::Left);
+    assert!(outcome.board_changed());
+    assert_eq!(outcome.score_delta, 4);
+    assert_eq!(
+        outcome.merges,
+        vec![Merge {
+            sources: (BoardCoordinate(1, 0), BoardCoordinate(2, 0)),
+            destination: BoardCoordinate(0, 0),
+            value: 4,
+        }],
+    );
+    assert_eq!(
+        outcome.slides,
+        vec![Slide {
+            from: BoardCoordinate(3, 0),
+            to: BoardCoordinate(1, 0),
+        }],
+    );
+}
+
+#[test]
+fn merge_against_the_edge_changes_the_board() {
+    let mut board = board_with_top_row([2, 2, 0, 0]);
+    let outcome = board.press(Direction::Left);
+    assert!(outcome.board_changed());
+    assert!(outcome.slides.is_empty());
+    assert_eq!(outcome.merges.len(), 1);
+    assert_eq!(outcome.merges[0].destination, BoardCoordinate(0, 0));
+}
+
+#[test]
+fn blocked_press_reports_no_change() {
+    let mut board = board_with_top_row([2, 4, 8, 16]);
+    let outcome = board.press(Direction::Left);
+    assert!(!outcome.board_changed());
+    assert_eq!(outcome.score_delta, 0);
+    assert_eq!(outcome.to_string(), "Left: nothing moved");
+}
+
+#[test]
+fn only_moves_that_change_the_board_count_as_turns() {
+    let mut board = board_with_top_row([2, 4, 0, 0]);
+    board.press(Direction::Left);
+    assert_eq!(board.move_count(), 0);
+    board.press(Direction::Right);
+    assert_eq!(board.move_count(), 1);
+}
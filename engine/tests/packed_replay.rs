@@ -0,0 +1,92 @@
+mod common;
+
+use common::record_game;
+use engine::{Board, ClaimedResult, Direction, RandomPlayer, Replay, ReplayError, Rules};
+
+fn claim_for(board: &Board) -> ClaimedResult {
+    ClaimedResult { score: board.score(), max_tile: board.max_tile() }
+}
+
+#[test]
+fn packing_round_trips() {
+    for (seed, rules) in [(1, Rules::classic()), (2, Rules::hard().with_size(5, 3)), (3, Rules::classic().with_size(6, 6))] {
+        let (replay, _) = record_game(seed, rules, &mut RandomPlayer::with_seed(seed), 1000);
+        let unpacked = Replay::from_bytes(&replay.to_bytes()).unwrap();
+        assert_eq!(unpacked, replay);
+    }
+}
+
+#[test]
+fn moves_take_two_bits_each() {
+    let (replay, _) = record_game(4, Rules::classic(), &mut RandomPlayer::with_seed(4), 1000);
+    let header_len = Replay::new(4, Rules::classic()).to_bytes().len();
+    assert_eq!(replay.to_bytes().len(), header_len + replay.moves.len().div_ceil(4));
+}
+
+#[test]
+fn timestamps_and_outcome_are_dropped() {
+    let (mut replay, board) = record_game(5, Rules::classic(), &mut RandomPlayer::with_seed(5), 50);
+    replay.moves[0].timestamp_ms = Some(250);
+    replay.record_outcome(&board);
+    let unpacked = Replay::from_bytes(&replay.to_bytes()).unwrap();
+    assert_eq!(unpacked.moves[0].timestamp_ms, None);
+    assert_eq!(unpacked.outcome, None);
+    let directions = |replay: &Replay| replay.moves.iter().map(|replay_move| replay_move.direction).collect::<Vec<_>>();
+    assert_eq!(directions(&unpacked), directions(&replay));
+}
+
+#[test]
+fn verifies_honest_claims() {
+    let (replay, board) = record_game(6, Rules::classic(), &mut RandomPlayer::with_seed(6), usize::MAX);
+    let unpacked = Replay::from_bytes(&replay.to_bytes()).unwrap();
+    assert_eq!(unpacked.verify(&claim_for(&board)), Ok(()));
+}
+
+#[test]
+fn rejects_forged_claims() {
+    let (replay, board) = record_game(7, Rules::classic(), &mut RandomPlayer::with_seed(7), usize::MAX);
+    let honest = claim_for(&board);
+
+    let inflated_score = ClaimedResult { score: honest.score + 4, ..honest };
+    assert_eq!(replay.verify(&inflated_score), Err(ReplayError::ClaimMismatch { claimed: inflated_score, actual: honest }));
+    let inflated_tile = ClaimedResult { max_tile: honest.max_tile * 2, ..honest };
+    assert!(matches!(replay.verify(&inflated_tile), Err(ReplayError::ClaimMismatch { .. })));
+
+    // Tampering with the moves themselves stops the replay from playing through, or changes where it ends up
+    let mut bytes = replay.to_bytes();
+    // Flip one move halfway through the game
+    let halfway = bytes.len() - (replay.moves.len() / 2).div_ceil(4);
+    bytes[halfway] ^= 0b01;
+    let tampered = Replay::from_bytes(&bytes).unwrap();
+    assert!(tampered.verify(&honest).is_err());
+}
+
+#[test]
+fn rejects_malformed_bytes() {
+    let (replay, _) = record_game(8, Rules::classic(), &mut RandomPlayer::with_seed(8), 100);
+    let bytes = replay.to_bytes();
+    assert!(matches!(Replay::from_bytes(&bytes[..bytes.len() - 1]), Err(ReplayError::Malformed(_))));
+    assert!(matches!(Replay::from_bytes(&bytes[..10]), Err(ReplayError::Malformed(_))));
+    assert!(matches!(Replay::from_bytes(b"{\"version\": 1}"), Err(ReplayError::Malformed(_))));
+
+    let mut future_version = bytes.clone();
+    future_version[4] = 2;
+    assert_eq!(Replay::from_bytes(&future_version), Err(ReplayError::UnsupportedVersion(2)));
+}
+
+#[test]
+fn direction_codes_cover_every_direction() {
+    let mut replay = Replay::new(0, Rules::classic());
+    for direction in Direction::ALL.into_iter().chain(Direction::ALL.into_iter().rev()) {
+        replay.record(direction, None);
+    }
+    assert_eq!(Replay::from_bytes(&replay.to_bytes()).unwrap(), replay);
+}
+
+#[test]
+fn rejects_headers_asking_for_enormous_boards() {
+    let mut bytes = Replay::new(1, Rules::classic()).to_bytes();
+    // The width and height follow the magic, the version and the seed
+    bytes[13..17].copy_from_slice(&[0xff; 4]);
+    assert_eq!(Replay::from_bytes(&bytes), Err(ReplayError::InvalidRules));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::record_game;
+use engine::{CyclicPlayer, Direction, Replay, ReplayError, Rules};
+
+#[test]
+fn replaying_reproduces_the_game() {
+    let (replay, board) = record_game(17, Rules::hard().with_size(5, 4), &mut CyclicPlayer::default(), 100);
+    let replayed = replay.play().unwrap();
+    assert_eq!(replayed.cells, board.cells);
+    assert_eq!(replayed.score(), board.score());
+    assert_eq!(replayed.move_count(), replay.moves.len());
+}
+
+#[test]
+fn replays_survive_json() {
+    let (mut replay, board) = record_game(3, Rules::classic(), &mut CyclicPlayer::default(), 40);
+    replay.record_outcome(&board);
+    replay.moves[0].timestamp_ms = Some(1500);
+    let parsed = Replay::from_json(&replay.to_json()).unwrap();
+    assert_eq!(parsed, replay);
+    assert!(parsed.play().is_ok());
+}
+
+#[test]
+fn observer_sees_every_move() {
+    let (replay, _) = record_game(8, Rules::classic(), &mut CyclicPlayer::default(), 30);
+    let mut seen = vec![];
+    replay.play_with(|replay_move, outcome, _| {
+        assert_eq!(replay_move.direction, outcome.direction);
+        seen.push(replay_move.direction);
+    }).unwrap();
+    assert_eq!(seen, replay.moves.iter().map(|replay_move| replay_move.direction).collect::<Vec<_>>());
+}
+
+#[test]
+fn diverging_replays_fail() {
+    let (mut replay, board) = record_game(6, Rules::classic(), &mut CyclicPlayer::default(), 40);
+    replay.record_outcome(&board);
+
+    // Claiming a different result
+    let mut forged = replay.clone();
+    forged.outcome.as_mut().unwrap().score += 4;
+    assert!(matches!(forged.play(), Err(ReplayError::OutcomeMismatch { .. })));
+
+    // Starting from a different seed sends the game somewhere else entirely
+    let mut reseeded = replay.clone();
+    reseeded.seed += 1;
+    assert!(reseeded.play().is_err());
+
+    // A move that can't have happened, since it doesn't change the board
+    let mut impossible = replay.clone();
+    let direction = Direction::ALL.into_iter().find(|direction| !board.legal_moves().contains(direction)).unwrap();
+    impossible.record(direction, None);
+    let move_idx = replay.moves.len();
+    assert_eq!(impossible.play().err(), Some(ReplayError::MoveDidNothing { move_idx, direction }));
+}
+
+#[test]
+fn rejects_malformed_replays() {
+    assert!(matches!(Replay::from_json("Left Left Up"), Err(ReplayError::Malformed(_))));
+    let (replay, _) = record_game(5, Rules::classic(), &mut CyclicPlayer::default(), 4);
+    let json = replay.to_json().replace("\"version\": 1", "\"version\": 99");
+    assert_eq!(Replay::from_json(&json), Err(ReplayError::UnsupportedVersion(99)));
+}
+
+#[test]
+fn rejects_boards_too_large_to_build() {
+    let replay = Replay::new(1, Rules::classic().with_size(1 << 20, 1 << 20));
+    assert_eq!(Replay::from_json(&replay.to_json()), Err(ReplayError::InvalidRules));
+    assert_eq!(replay.starting_board().err(), Some(ReplayError::InvalidRules));
+    assert_eq!(replay.play().err(), Some(ReplayError::InvalidRules));
+    let overflowing = Replay::new(1, Rules::classic().with_size(usize::MAX, 2));
+    assert_eq!(overflowing.play().err(), Some(ReplayError::InvalidRules));
+}
+
+#[test]
+fn rejects_unplayable_rules_when_loading() {
+    let mut no_cells = Rules::classic();
+    no_cells.width = 0;
+    let mut nothing_spawns = Rules::classic();
+    for spawn in nothing_spawns.spawn_table.iter_mut() {
+        spawn.weight = 0;
+    }
+    for rules in [no_cells, nothing_spawns] {
+        let json = Replay::new(1, rules).to_json();
+        assert_eq!(Replay::from_json(&json), Err(ReplayError::InvalidRules));
+    }
+}
@@ -0,0 +1,61 @@
+use engine::{Board, CellContents, Rules, SpawnWeight};
+
+/// Spawns a tile on an otherwise empty board many times over, and counts how often each value comes up
+fn spawned_value_counts(rules: Rules, samples: usize) -> Vec<(usize, usize)> {
+    let mut board = Board::with_seed_and_rules(5, rules.clone());
+    let mut counts = rules.spawn_table.iter().map(|spawn| (spawn.value, 0)).collect::<Vec<_>>();
+    for _ in 0..samples {
+        let coords = board.spawn_tile_in_random_location().unwrap();
+        let value = board.cells.iter().find(|cell| cell.coords == coords).unwrap().contents.unwrap();
+        counts.iter_mut().find(|(v, _)| *v == value).unwrap().1 += 1;
+        board.empty();
+    }
+    counts
+}
+
+#[test]
+fn classic_rules_mostly_spawn_twos() {
+    let counts = spawned_value_counts(Rules::classic(), 10_000);
+    let fours = counts.iter().find(|(value, _)| *value == 4).unwrap().1;
+    assert!((800..1200).contains(&fours), "Expected roughly 10% fours, got {counts:?}");
+}
+
+#[test]
+fn hard_rules_spawn_twos_and_fours_evenly() {
+    let counts = spawned_value_counts(Rules::hard(), 10_000);
+    let fours = counts.iter().find(|(value, _)| *value == 4).unwrap().1;
+    assert!((4500..5500).contains(&fours), "Expected roughly 50% fours, got {counts:?}");
+}
+
+#[test]
+fn custom_spawn_table() {
+    let rules = Rules {
+        spawn_table: vec![
+            SpawnWeight { value: 8, weight: 1 },
+            SpawnWeight { value: 2, weight: 0 },
+        ],
+        ..Rules::default()
+    };
+    assert_eq!(spawned_value_counts(rules, 100), vec![(8, 100), (2, 0)]);
+}
+
+#[test]
+fn tiles_per_turn() {
+    let mut board = Board::with_seed_and_rules(3, Rules { tiles_per_turn: 3, ..Rules::default() });
+    assert_eq!(board.spawn_tiles_for_turn().len(), 3);
+    assert_eq!(board.cells.iter().filter(|cell| cell.contents != CellContents::Empty).count(), 3);
+
+    // Stops once the board is full
+    for _ in 0..5 {
+        board.spawn_tiles_for_turn();
+    }
+    assert!(board.is_full());
+    assert!(board.spawn_tiles_for_turn().is_empty());
+}
+
+#[test]
+fn presets_by_name() {
+    assert_eq!(Rules::try_from("classic"), Ok(Rules::classic()));
+    assert_eq!(Rules::try_from("hard"), Ok(Rules::hard()));
+    assert_eq!(Rules::try_from("impossible"), Err(()));
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{board_from_rows, cycle_through_directions, start_game, start_game_with_rules};
+use engine::{Board, BoardCoordinate, LoadError, Rules, SAVE_FORMAT_VERSION};
+use serde_json::{json, Value};
+
+/// Tries to load a save that's been tampered with, returning the reason it was turned down (if it was)
+fn rejection(board: &Board, edit: impl FnOnce(&mut Value)) -> Option<LoadError> {
+    let mut saved: Value = serde_json::from_str(&board.to_json()).unwrap();
+    edit(&mut saved);
+    Board::from_json(&saved.to_string()).err()
+}
+
+#[test]
+fn round_trip_preserves_the_game() {
+    let mut board = start_game_with_rules(7, Rules::hard().with_size(5, 3));
+    cycle_through_directions(&mut board, 20, |_, _| {});
+
+    let loaded = Board::from_json(&board.to_json()).unwrap();
+    assert_eq!(loaded.cells, board.cells);
+    assert_eq!(loaded.score(), board.score());
+    assert_eq!(loaded.best_score(), board.best_score());
+    assert_eq!(loaded.move_count(), board.move_count());
+    assert_eq!(loaded.seed(), board.seed());
+    assert_eq!(loaded.rules(), board.rules());
+}
+
+#[test]
+fn loaded_game_spawns_the_same_tiles() {
+    let mut board = start_game(42);
+    cycle_through_directions(&mut board, 20, |_, _| {});
+    let mut loaded = Board::from_json(&board.to_json()).unwrap();
+
+    // Both copies should carry on identically, since the generator's position was saved along with the tiles
+    cycle_through_directions(&mut board, 20, |_, _| {});
+    cycle_through_directions(&mut loaded, 20, |_, _| {});
+    assert_eq!(loaded.cells, board.cells);
+    assert_eq!(loaded.score(), board.score());
+}
+
+#[test]
+fn tiles_are_saved_as_rows_of_values() {
+    let board = board_from_rows([
+        [2, 0, 0, 0],
+        [0, 4, 0, 0],
+        [0, 0, 0, 0],
+        [0, 0, 0, 2048],
+    ]);
+    let saved: Value = serde_json::from_str(&board.to_json()).unwrap();
+    assert_eq!(saved["version"], json!(SAVE_FORMAT_VERSION));
+    assert_eq!(saved["tiles"], json!([[2, 0, 0, 0], [0, 4, 0, 0], [0, 0, 0, 0], [0, 0, 0, 2048]]));
+}
+
+#[test]
+fn rejects_invalid_tiles() {
+    let board = Board::with_seed(1);
+    // Not a power of two
+    assert_eq!(
+        rejection(&board, |saved| saved["tiles"][1][2] = json!(6)),
+        Some(LoadError::InvalidTile { coords: BoardCoordinate(2, 1), value: 6 }),
+    );
+    // Smaller than anything that can spawn
+    assert_eq!(
+        rejection(&board, |saved| saved["tiles"][0][0] = json!(1)),
+        Some(LoadError::InvalidTile { coords: BoardCoordinate(0, 0), value: 1 }),
+    );
+    // 2^17 is the largest tile that fits on a 4x4 board when fours can spawn, but 2^18 isn't reachable
+    assert_eq!(rejection(&board, |saved| saved["tiles"][3][3] = json!(1 << 17)), None);
+    assert_eq!(
+        rejection(&board, |saved| saved["tiles"][3][3] = json!(1 << 18)),
+        Some(LoadError::InvalidTile { coords: BoardCoordinate(3, 3), value: 1 << 18 }),
+    );
+}
+
+#[test]
+fn rejects_tiles_that_dont_match_the_board_size() {
+    let board = Board::with_seed(1);
+    assert_eq!(
+        rejection(&board, |saved| saved["tiles"][2] = json!([0, 0, 0])),
+        Some(LoadError::WrongSize { width: 4, height: 4 }),
+    );
+    assert_eq!(
+        rejection(&board, |saved| saved["rules"]["height"] = json!(5)),
+        Some(LoadError::WrongSize { width: 4, height: 5 }),
+    );
+}
+
+#[test]
+fn rejects_unplayable_rules() {
+    let board = Board::with_seed(1);
+    assert_eq!(
+        rejection(&board, |saved| saved["rules"]["spawn_table"] = json!([])),
+        Some(LoadError::InvalidRules),
+    );
+    assert_eq!(
+        rejection(&board, |saved| saved["rules"]["spawn_table"] = json!([{ "value": 3, "weight": 1 }])),
+        Some(LoadError::InvalidRules),
+    );
+    // Weights that add up past u32::MAX would overflow as soon as a tile spawned
+    let overflowing = json!([{ "value": 2, "weight": u32::MAX }, { "value": 4, "weight": u32::MAX }]);
+    assert_eq!(
+        rejection(&board, |saved| saved["rules"]["spawn_table"] = overflowing.clone()),
+        Some(LoadError::InvalidRules),
+    );
+    assert_eq!(rejection(&board, |saved| saved["rules"]["goal_tile"] = json!(1)), Some(LoadError::InvalidRules));
+}
+
+#[test]
+fn rejects_other_versions_and_garbage() {
+    let board = Board::with_seed(1);
+    assert_eq!(
+        rejection(&board, |saved| saved["version"] = json!(SAVE_FORMAT_VERSION + 1)),
+        Some(LoadError::UnsupportedVersion(SAVE_FORMAT_VERSION + 1)),
+    );
+    assert!(matches!(Board::from_json("|  2 |  4 |").err(), Some(LoadError::Malformed(_))));
+    assert!(matches!(rejection(&board, |saved| saved["rng"] = json!(null)), Some(LoadError::Malformed(_))));
+}
@@ -0,0 +1,56 @@
+use engine::{Board, CellContents, Direction, Rules};
+
+fn fill_top_row(board: &mut Board, row: [usize; 4]) {
+    for (col_idx, value) in row.into_iter().enumerate() {
+        board.cells[col_idx].contents = match value {
+            0 => CellContents::Empty,
+            value => CellContents::Occupied(value),
+        };
+    }
+}
+
+#[test]
+fn score_accumulates_merged_values() {
+    let mut board = Board::new();
+    fill_top_row(&mut board, [2, 2, 4, 4]);
+    board.press(Direction::Left);
+    assert_eq!(board.score(), 4 + 8);
+    board.press(Direction::Left);
+    // The 4 and 8 from the first move don't merge, so nothing is earned
+    assert_eq!(board.score(), 12);
+    assert_eq!(board.best_score(), board.score());
+}
+
+#[test]
+fn best_score_survives_reset() {
+    let mut board = Board::new();
+    fill_top_row(&mut board, [8, 8, 0, 0]);
+    board.press(Direction::Right);
+    board.empty();
+    assert_eq!(board.score(), 0);
+    assert_eq!(board.best_score(), 16);
+
+    // A worse game doesn't lower the best score
+    fill_top_row(&mut board, [2, 2, 0, 0]);
+    board.press(Direction::Left);
+    assert_eq!(board.score(), 4);
+    assert_eq!(board.best_score(), 16);
+}
+
+#[test]
+fn reaching_the_goal_is_reported_once() {
+    let mut board = Board::with_rules(Rules { goal_tile: 16, ..Rules::default() });
+    fill_top_row(&mut board, [8, 8, 4, 4]);
+    let outcome = board.press(Direction::Left);
+    assert!(outcome.reached_goal);
+
+    // Making another goal tile in the same game isn't a fresh win
+    fill_top_row(&mut board, [8, 8, 0, 0]);
+    let outcome = board.press(Direction::Left);
+    assert!(!outcome.reached_goal);
+
+    // But it is once a new game has started
+    board.empty();
+    fill_top_row(&mut board, [8, 8, 0, 0]);
+    assert!(board.press(Direction::Left).reached_goal);
+}
@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+
+mod common;
+
+use common::{cycle_through_directions, start_game};
+use engine::{board, Board, BoardCoordinate, Direction, TileEvent};
+
+fn id_at(board: &Board, x: usize, y: usize) -> engine::TileId {
+    board.tile_id_at(BoardCoordinate(x, y)).unwrap()
+}
+
+#[test]
+fn tiles_keep_their_ids_as_they_slide() {
+    let mut board = board!("..1./..../.2../....");
+    let (first, second) = (id_at(&board, 2, 0), id_at(&board, 1, 2));
+    board.press(Direction::Left);
+    assert_eq!(id_at(&board, 0, 0), first);
+    assert_eq!(id_at(&board, 0, 2), second);
+    assert_eq!(board.tile_id_at(BoardCoordinate(2, 0)), None);
+    assert_eq!(
+        board.tile_events(),
+        &[
+            TileEvent::Moved { id: first, from: BoardCoordinate(2, 0), to: BoardCoordinate(0, 0) },
+            TileEvent::Moved { id: second, from: BoardCoordinate(1, 2), to: BoardCoordinate(0, 2) },
+        ],
+    );
+}
+
+#[test]
+fn merged_tile_keeps_the_id_of_the_tile_it_merged_into() {
+    let mut board = board!(".1.1");
+    let (nearer, further) = (id_at(&board, 3, 0), id_at(&board, 1, 0));
+    board.press(Direction::Right);
+    assert_eq!(id_at(&board, 3, 0), nearer);
+    assert_eq!(board.tiles().len(), 1);
+    assert_eq!(
+        board.tile_events(),
+        &[TileEvent::Merged { id: further, into: nearer, from: BoardCoordinate(1, 0), to: BoardCoordinate(3, 0), value: 4 }],
+    );
+}
+
+#[test]
+fn merge_target_can_slide_too() {
+    let mut board = board!("..11");
+    let (left, right) = (id_at(&board, 2, 0), id_at(&board, 3, 0));
+    board.press(Direction::Left);
+    assert_eq!(id_at(&board, 0, 0), left);
+    assert_eq!(
+        board.tile_events(),
+        &[
+            TileEvent::Moved { id: left, from: BoardCoordinate(2, 0), to: BoardCoordinate(0, 0) },
+            TileEvent::Merged { id: right, into: left, from: BoardCoordinate(3, 0), to: BoardCoordinate(0, 0), value: 4 },
+        ],
+    );
+}
+
+#[test]
+fn spawns_get_fresh_ids_and_are_reported() {
+    let mut board = board!("1.../..../..../....");
+    let existing = id_at(&board, 0, 0);
+    board.press(Direction::Right);
+    let coords = board.spawn_tile_in_random_location().unwrap();
+    let spawned = board.tile_id_at(coords).unwrap();
+    assert_ne!(spawned, existing);
+    assert!(matches!(board.tile_events().last(), Some(TileEvent::Spawned { id, at, .. }) if *id == spawned && *at == coords));
+
+    // The next press starts a fresh list of events
+    board.press(Direction::Left);
+    assert!(board.tile_events().iter().all(|event| !matches!(event, TileEvent::Spawned { .. })));
+}
+
+#[test]
+fn emptying_the_board_removes_every_tile() {
+    let mut board = board!("1.2./..../..3./....");
+    let ids = board.tiles().iter().map(|tile| tile.id).collect::<HashSet<_>>();
+    board.press(Direction::Up);
+    board.empty();
+    assert!(board.tiles().is_empty());
+    let removed = board.tile_events().iter().filter_map(|event| match event {
+        TileEvent::Removed { id, .. } => Some(*id),
+        _ => None,
+    }).collect::<HashSet<_>>();
+    assert_eq!(removed, ids);
+}
+
+#[test]
+fn ids_stay_unique_through_a_game() {
+    cycle_through_directions(&mut start_game(21), 200, |board, _| {
+        let tiles = board.tiles();
+        assert_eq!(tiles.iter().map(|tile| tile.id).collect::<HashSet<_>>().len(), tiles.len());
+        assert_eq!(tiles.len(), board.cells.iter().filter(|cell| !cell.is_empty()).count());
+    });
+}
+
+#[test]
+fn events_line_up_with_the_move_outcome() {
+    // Spawning adds its own events, but never a merge
+    cycle_through_directions(&mut start_game(34), 100, |board, outcome| {
+        let merges = board.tile_events().iter().filter(|event| matches!(event, TileEvent::Merged { .. })).count();
+        assert_eq!(merges, outcome.merges.len());
+    });
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::{cycle_through_directions, start_game};
+use engine::{board, Board, Direction};
+
+/// What the stdin program printed for `echo k | game --seed 3`: the starting board, then a single press of Up
+const NATIVE_TRANSCRIPT: &str = "Game started!
+Seed: 3
+
+Score: 0    Best: 0
+---------------------------------------------
+|          |          |          |          |
+|          |          |          |          |
+|          |          |          |          |
+---------------------------------------------
+|          |          |          |          |
+|    2     |          |          |          |
+|          |          |          |          |
+---------------------------------------------
+|          |          |          |          |
+|          |          |          |          |
+|          |          |          |          |
+---------------------------------------------
+|          |          |          |          |
+|    2     |          |          |          |
+|          |          |          |          |
+---------------------------------------------
+
+Up: 0 slid, 1 merged, +4 points
+  (0, 1) + (0, 3) -> 4 at (0, 0)
+
+Score: 4    Best: 4
+---------------------------------------------
+|          |          |          |          |
+|    4     |          |          |          |
+|          |          |          |          |
+---------------------------------------------
+|          |          |          |          |
+|          |          |          |          |
+|          |          |          |          |
+---------------------------------------------
+|          |          |          |          |
+|          |          |          |          |
+|          |          |          |          |
+---------------------------------------------
+|          |          |          |          |
+|          |          |          |    2     |
+|          |          |          |          |
+---------------------------------------------
+
+";
+
+/// The earlier browser builds skipped the leading newline and the score, and didn't end with a newline either.
+/// Text copied out of the page's `<pre>` also keeps its indentation.
+const WASM_RENDERING: &str = "        ---------------------------------------------
+        |          |          |          |          |
+        |   16     |          |   2048   |          |
+        |          |          |          |          |
+        ---------------------------------------------
+        |          |          |          |          |
+        |          |          |          |    2     |
+        |          |          |          |          |
+        ---------------------------------------------";
+
+#[test]
+fn reads_the_native_rendering() {
+    let board = Board::from_rendering(NATIVE_TRANSCRIPT).unwrap();
+    assert_eq!(board.cells, board!("..../1.../..../1...").cells);
+    assert_eq!(board.score(), 0);
+}
+
+#[test]
+fn reads_the_wasm_rendering() {
+    let board = Board::from_rendering(WASM_RENDERING).unwrap();
+    assert_eq!((board.width(), board.height()), (4, 2));
+    assert_eq!(board.cells, board!("4.b./...1").cells);
+}
+
+#[test]
+fn reads_every_board_in_a_transcript() {
+    let boards = Board::all_from_rendering(NATIVE_TRANSCRIPT).unwrap();
+    assert_eq!(boards.len(), 2);
+    assert_eq!(boards[1].cells, board!("2.../..../..../...1").cells);
+    assert_eq!((boards[1].score(), boards[1].best_score()), (4, 4));
+
+    // The transcript should agree with what the engine does with the same move
+    let mut replayed = boards[0].clone();
+    replayed.press(Direction::Up);
+    assert_eq!(replayed.to_notation(), "2.../..../..../....");
+}
+
+#[test]
+fn round_trips_through_display() {
+    cycle_through_directions(&mut start_game(11), 60, |board, _| {
+        let parsed = Board::from_rendering(&board.to_string()).unwrap();
+        assert_eq!(parsed.cells, board.cells);
+        assert_eq!(parsed.score(), board.score());
+    });
+}
+
+#[test]
+fn rejects_garbled_renderings() {
+    assert!(Board::from_rendering("Game started!").is_none());
+    // Cut off halfway through a row
+    let truncated = WASM_RENDERING.lines().take(6).collect::<Vec<_>>().join("\n");
+    assert!(Board::from_rendering(&truncated).is_none());
+    // Not a tile
+    assert!(Board::from_rendering(&WASM_RENDERING.replace("2048", "2047")).is_none());
+    // Rows of different widths
+    assert!(Board::from_rendering(&WASM_RENDERING.replacen("|    2     |", "|", 1)).is_none());
+}
@@ -0,0 +1,109 @@
+//! Trains an n-tuple network by self-play, for the `ntuple` player of the game to load with `--weights`.
+//!
+//! Usage: train --weights FILE [--games N] [--checkpoint-every N] [--learning-rate RATE] [--seed SEED] [--resume]
+use std::fs;
+use std::io;
+use std::path::Path;
+use engine::{Board, NTupleNetwork, Rules, DEFAULT_GOAL_TILE, DEFAULT_LEARNING_RATE};
+
+/// Settings passed on the command line
+struct TrainingOptions {
+    /// Where the weights are checkpointed to
+    weights: String,
+    games: u64,
+    /// How many games to play between each checkpoint (and progress report)
+    checkpoint_every: u64,
+    learning_rate: f32,
+    /// Plays the exact same games when set. Otherwise, a fresh seed is picked (and printed) at startup.
+    seed: Option<u64>,
+    /// Carries on training the weights already in the file. Without it, an existing file is never overwritten.
+    resume: bool,
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn parse_value<T: std::str::FromStr>(arg: &str, value: Option<String>) -> io::Result<T> {
+    let value = value.ok_or_else(|| invalid_input(format!("{arg} requires a value")))?;
+    value.parse().map_err(|_| invalid_input(format!("Invalid value for {arg}: {value}")))
+}
+
+impl TrainingOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> io::Result<Self> {
+        let mut weights = None;
+        let mut options = Self {
+            weights: String::new(),
+            games: 10_000,
+            checkpoint_every: 1_000,
+            learning_rate: DEFAULT_LEARNING_RATE,
+            seed: None,
+            resume: false,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_ref() {
+                "--weights" => weights = Some(args.next().ok_or_else(|| invalid_input("--weights requires a file".to_string()))?),
+                "--games" => options.games = parse_value(&arg, args.next())?,
+                "--checkpoint-every" => options.checkpoint_every = parse_value(&arg, args.next())?,
+                "--learning-rate" => options.learning_rate = parse_value(&arg, args.next())?,
+                "--seed" => options.seed = Some(parse_value(&arg, args.next())?),
+                "--resume" => options.resume = true,
+                _ => return Err(invalid_input(format!("Unrecognized argument: {arg}"))),
+            }
+        }
+        options.weights = weights.ok_or_else(|| invalid_input("--weights is required".to_string()))?;
+        if options.games == 0 {
+            return Err(invalid_input("--games must be at least 1".to_string()));
+        }
+        if options.checkpoint_every == 0 {
+            return Err(invalid_input("--checkpoint-every must be at least 1".to_string()));
+        }
+        Ok(options)
+    }
+}
+
+fn main() -> io::Result<()> {
+    let options = TrainingOptions::from_args(std::env::args().skip(1))?;
+    let mut network = if options.resume {
+        let bytes = fs::read(&options.weights)?;
+        NTupleNetwork::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
+    } else if Path::new(&options.weights).exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists; pass --resume to keep training it", options.weights),
+        ));
+    } else {
+        NTupleNetwork::default()
+    };
+    println!("Starting from {} games of training", network.games_trained);
+
+    // Every game is drawn from the session's seed, as in the game itself
+    let mut board = match options.seed {
+        Some(seed) => Board::with_seed_and_rules(seed, Rules::classic()),
+        None => Board::with_rules(Rules::classic()),
+    };
+    println!("Seed: {}", board.seed());
+
+    let (mut total_score, mut wins, mut games_since_checkpoint) = (0, 0, 0);
+    for game_idx in 1..=options.games {
+        board.start_new_game(None);
+        network.train_on_game(&mut board, options.learning_rate);
+
+        total_score += board.score();
+        wins += (board.max_tile() >= DEFAULT_GOAL_TILE) as usize;
+        games_since_checkpoint += 1;
+        if game_idx % options.checkpoint_every == 0 || game_idx == options.games {
+            println!(
+                "{} games: averaging {} points, reaching {} in {:.1}% of games",
+                network.games_trained,
+                total_score / games_since_checkpoint,
+                DEFAULT_GOAL_TILE,
+                wins as f64 * 100.0 / games_since_checkpoint as f64,
+            );
+            fs::write(&options.weights, network.to_bytes())?;
+            (total_score, wins, games_since_checkpoint) = (0, 0, 0);
+        }
+    }
+    println!("Saved weights to {}", options.weights);
+    Ok(())
+}
@@ -1,28 +1,194 @@
+use std::fs;
 use std::io;
 use std::io::BufRead;
-use crate::board::Board;
-use input::Direction;
-mod board;
-mod input;
+use engine::{Board, ClaimedResult, Direction, Evaluation, EvaluationWeights, History};
+use crate::options::Options;
+use crate::recorder::{read_replay, Recorder};
+mod options;
+mod recorder;
+
+/// Shows the board at the start of a game, and returns whether there's any move to make. On a small enough board,
+/// the starting tiles might not leave one.
+fn show_starting_board(board: &Board) -> bool {
+    println!("{board}");
+    let playable = board.has_any_legal_move();
+    if !playable {
+        println!("Game over!");
+    }
+    playable
+}
+
+/// Clears the board for the next game and shows it. Each game gets its own seed, so that it can be replayed on its
+/// own.
+fn start_next_game(board: &mut Board) {
+    let seed = board.start_new_game(None);
+    println!("Seed: {seed}");
+    show_starting_board(board);
+}
+
+/// Writes out the replay of a game, if the player asked for every game to be recorded with `--record`
+fn record_finished_game(options: &Options, recorder: &Recorder, board: &Board) {
+    let Some(path) = options.record.as_ref() else {
+        return;
+    };
+    match recorder.write(path, board) {
+        Ok(()) => println!("Recorded to {path}"),
+        Err(e) => println!("Couldn't record to {path}: {e}"),
+    }
+}
+
+/// Plays a recorded game back, move by move, and fails if it doesn't go the way the recording says it did (or
+/// doesn't end with the claimed result, if there is one)
+fn run_replay(path: &str, claim: Option<ClaimedResult>) -> io::Result<()> {
+    let replay = read_replay(path)?;
+    let starting_board = replay.starting_board()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    println!("Replaying {} moves", replay.moves.len());
+    println!("Seed: {}", replay.seed);
+    println!("{starting_board}");
+    let result = replay.play_with(|_, outcome, board| {
+        println!("{outcome}");
+        println!("{board}");
+    });
+    let board = result.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Replay diverged: {e}")))?;
+    println!("Replay finished with {} points", board.score());
+    if let Some(claim) = claim {
+        replay.verify(&claim).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        println!("Verified {claim}");
+    }
+    Ok(())
+}
 
 fn main() -> io::Result<()> {
+    let options = Options::from_args(std::env::args().skip(1))?;
+    if let Some(path) = options.replay.as_ref() {
+        return run_replay(path, options.claim);
+    }
     println!("Game started!");
-    let mut board = Board::new();
+    let mut board = Board::with_rules(options.rules.clone());
 
-    // Spawn a couple tiles
-    board.spawn_tile_in_random_location();
-    board.spawn_tile_in_random_location();
+    // Spawn a couple tiles, and let the player know how to get this game back
+    let seed = board.start_new_game(options.seed);
+    println!("Seed: {seed}");
+
+    // Lets the player write out the moves of the current game with 'record <file>'
+    let mut recorder = Recorder::new(&board);
 
     // Show the initial state of the board
-    println!("{board}");
+    let playable = show_starting_board(&board);
+
+    // Lets the player take back moves with 'u', and replay them with 'r'
+    let mut history = History::new();
+
+    // Once the player wins, we wait for them to decide what to do next before handling any more moves
+    let mut awaiting_decision_after_win = false;
+
+    // With --player, the computer types in the moves instead of reading them from stdin
+    let mut automated_player = options.automated_player()?;
+    if !playable && automated_player.is_some() {
+        record_finished_game(&options, &recorder, &board);
+        return Ok(());
+    }
 
     let stdin = io::stdin();
-    for maybe_next_line_of_input in stdin.lock().lines() {
-        if let Err(e) = maybe_next_line_of_input {
-            return Err(e);
+    let mut lines_of_input = stdin.lock().lines();
+    loop {
+        let next_line_of_input = match automated_player.as_mut() {
+            // Carry on after winning, to see how far the player gets
+            Some(_) if awaiting_decision_after_win => "c".to_string(),
+            Some(player) => format!("{:?}", player.choose(&board)),
+            None => match lines_of_input.next() {
+                Some(maybe_next_line_of_input) => maybe_next_line_of_input?,
+                None => break,
+            },
+        };
+        if awaiting_decision_after_win {
+            match next_line_of_input.as_ref() {
+                "c" => {
+                    println!("Keep going!");
+                    println!("{board}");
+                }
+                "n" => {
+                    record_finished_game(&options, &recorder, &board);
+                    println!("Game started!");
+                    start_next_game(&mut board);
+                    history.clear();
+                    recorder = Recorder::new(&board);
+                }
+                _ => {
+                    println!("Enter 'c' to keep playing, or 'n' to start a new game");
+                    continue;
+                }
+            }
+            awaiting_decision_after_win = false;
+            continue;
+        }
+
+        match next_line_of_input.as_ref() {
+            "u" => {
+                if history.undo(&mut board) {
+                    recorder.undo();
+                    println!("Undo");
+                    println!("{board}");
+                } else {
+                    println!("Nothing to undo");
+                }
+                continue;
+            }
+            "r" => {
+                if history.redo(&mut board) {
+                    recorder.redo();
+                    println!("Redo");
+                    println!("{board}");
+                } else {
+                    println!("Nothing to redo");
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some(path) = next_line_of_input.strip_prefix("record ") {
+            match recorder.write(path, &board) {
+                Ok(()) => println!("Recorded to {path}"),
+                Err(e) => println!("Couldn't record to {path}: {e}"),
+            }
+            continue;
+        }
+
+        // 'save <file>' and 'load <file>' keep a game around between runs
+        if let Some(path) = next_line_of_input.strip_prefix("save ") {
+            match fs::write(path, board.to_json()) {
+                Ok(()) => println!("Saved to {path}"),
+                Err(e) => println!("Couldn't save to {path}: {e}"),
+            }
+            continue;
+        }
+        if let Some(path) = next_line_of_input.strip_prefix("load ") {
+            let loaded = fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| Board::from_json(&json).map_err(|e| e.to_string()));
+            match loaded {
+                Ok(loaded_board) => {
+                    board = loaded_board;
+                    // The moves we remember belong to a different game
+                    history.clear();
+                    // And we don't know the moves that led up to this one
+                    recorder.abandon();
+                    println!("Loaded {path}");
+                    println!("{board}");
+                }
+                Err(e) => println!("Couldn't load {path}: {e}"),
+            }
+            continue;
+        }
+
+        // 'eval' shows how the searching players would score the board as it stands
+        if next_line_of_input == "eval" {
+            println!("{}", Evaluation::of(&board).breakdown(&EvaluationWeights::default()));
+            continue;
         }
 
-        let next_line_of_input = maybe_next_line_of_input.unwrap();
         let direction = match Direction::try_from(next_line_of_input.as_ref()) {
             Ok(d) => d,
             Err(_) => {
@@ -30,20 +196,53 @@ fn main() -> io::Result<()> {
                 continue;
             },
         };
-        println!("Processing {direction:?}");
-        board.press(direction);
-        if board.is_full() {
+        let board_before_move = board.clone();
+        let outcome = board.press(direction);
+        if !outcome.board_changed() {
+            // Don't spawn a new tile, otherwise the player could fill up the board just by pressing into a wall
+            println!("Nothing moved");
+            if automated_player.is_some() {
+                // The player only passes up every legal move when there aren't any left, so the game is over
+                println!("Game over!");
+                record_finished_game(&options, &recorder, &board);
+                return Ok(());
+            }
+            continue;
+        }
+        println!("{outcome}");
+        history.record(board_before_move);
+        recorder.record(direction);
+        board.spawn_tiles_for_turn();
+        if outcome.reached_goal {
+            println!("You reached {}!", board.goal_tile());
+            if board.has_any_legal_move() {
+                println!("{board}");
+                println!("Enter 'c' to keep playing, or 'n' to start a new game");
+                awaiting_decision_after_win = true;
+                continue;
+            }
+        }
+        if !board.has_any_legal_move() {
+            // Show the final state of the board before starting over
+            println!("{board}");
             println!("Game over!");
-            // Reset to an empty board
-            board.empty();
-            // And spawn an initial tile (the second will be spawned just below)
-            board.spawn_tile_in_random_location();
+            record_finished_game(&options, &recorder, &board);
+            if automated_player.is_some() {
+                // An automated player plays a single game
+                return Ok(());
+            }
+            // Reset to an empty board, with a couple of initial tiles
+            start_next_game(&mut board);
+            history.clear();
+            recorder = Recorder::new(&board);
+            continue;
         }
 
-        board.spawn_tile_in_random_location();
         // Show the new state of the board
         println!("{board}");
     }
 
+    // Out of input, so the current game ends here
+    record_finished_game(&options, &recorder, &board);
     Ok(())
 }
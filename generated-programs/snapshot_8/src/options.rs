@@ -0,0 +1,206 @@
+use std::fs;
+use std::io;
+use std::time::Duration;
+use engine::{
+    ClaimedResult, CornerPlayer, CyclicPlayer, Expectimax, GreedyPlayer, MonteCarlo, NTupleNetwork, NTuplePlayer,
+    RandomPlayer, Rules, Strategy, DEFAULT_ROLLOUT_COUNT, DEFAULT_ROLLOUT_DEPTH, DEFAULT_SEARCH_DEPTH,
+};
+
+/// The automated players that can take over from stdin
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(crate) enum Player {
+    Random,
+    Corner,
+    Greedy,
+    Cyclic,
+    Expectimax,
+    MonteCarlo,
+    NTuple,
+}
+
+impl TryFrom<&str> for Player {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "random" => Ok(Self::Random),
+            "corner" => Ok(Self::Corner),
+            "greedy" => Ok(Self::Greedy),
+            "cyclic" => Ok(Self::Cyclic),
+            "expectimax" => Ok(Self::Expectimax),
+            "monte-carlo" => Ok(Self::MonteCarlo),
+            "ntuple" => Ok(Self::NTuple),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Settings passed on the command line
+#[derive(Debug, Default)]
+pub(crate) struct Options {
+    /// Replays the exact same game when set. Otherwise, a fresh seed is picked (and printed) at startup.
+    pub(crate) seed: Option<u64>,
+    /// Which preset to play with: either `classic` or `hard`
+    pub(crate) rules: Rules,
+    /// Overrides the size of the board, given as `WIDTHxHEIGHT`, or just `SIZE` for a square board
+    pub(crate) size: Option<(usize, usize)>,
+    /// Writes a replay of each game to this file as it ends
+    pub(crate) record: Option<String>,
+    /// Plays back the replay in this file instead of starting a game. The replay brings its own seed and rules.
+    pub(crate) replay: Option<String>,
+    /// The result the replay is meant to end with, given as `SCORE:MAX_TILE`. The replay fails if it doesn't.
+    pub(crate) claim: Option<ClaimedResult>,
+    /// Lets the computer play a game by itself instead of reading moves from stdin. Picked with `--player`, or
+    /// `--ai` for short.
+    pub(crate) player: Option<Player>,
+    /// How many moves ahead the player looks: how deep expectimax searches, or how long each random game played by
+    /// monte-carlo runs for
+    pub(crate) search_depth: Option<usize>,
+    /// How long expectimax may think about each move, given in milliseconds
+    pub(crate) think_time: Option<Duration>,
+    /// How many random games monte-carlo plays out from each move
+    pub(crate) rollouts: Option<usize>,
+    /// The weight file the ntuple player loads, as written by the `train` binary
+    pub(crate) weights: Option<String>,
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn parse_size(value: &str) -> Option<(usize, usize)> {
+    let (width, height) = match value.split_once('x') {
+        Some((width, height)) => (width.parse().ok()?, height.parse().ok()?),
+        None => {
+            let size = value.parse().ok()?;
+            (size, size)
+        }
+    };
+    // Rule out boards too large to build, as well as empty ones
+    if !Rules::classic().with_size(width, height).is_playable() {
+        return None;
+    }
+    Some((width, height))
+}
+
+fn parse_claim(value: &str) -> Option<ClaimedResult> {
+    let (score, max_tile) = value.split_once(':')?;
+    Some(ClaimedResult { score: score.parse().ok()?, max_tile: max_tile.parse().ok()? })
+}
+
+impl Options {
+    /// The player picked with `--player`, set up as the other options ask
+    pub(crate) fn automated_player(&self) -> io::Result<Option<Box<dyn Strategy>>> {
+        let Some(player) = self.player else {
+            return Ok(None);
+        };
+        Ok(match player {
+            Player::Random => Some(Box::new(match self.seed {
+                Some(seed) => RandomPlayer::with_seed(seed),
+                None => RandomPlayer::default(),
+            })),
+            Player::Corner => Some(Box::new(CornerPlayer)),
+            Player::Greedy => Some(Box::new(GreedyPlayer)),
+            Player::Cyclic => Some(Box::new(CyclicPlayer::default())),
+            Player::Expectimax => {
+                let depth = self.search_depth.unwrap_or(DEFAULT_SEARCH_DEPTH);
+                Some(Box::new(match self.think_time {
+                    Some(think_time) => Expectimax::with_time_budget(depth, think_time),
+                    None => Expectimax::with_depth(depth),
+                }))
+            }
+            Player::MonteCarlo => {
+                // Follow --seed, so that the whole game (the player's moves included) can be played again
+                let player = match self.seed {
+                    Some(seed) => MonteCarlo::with_seed(seed),
+                    None => MonteCarlo::default(),
+                };
+                let rollouts = self.rollouts.unwrap_or(DEFAULT_ROLLOUT_COUNT);
+                Some(Box::new(player.with_rollouts(rollouts, self.search_depth.unwrap_or(DEFAULT_ROLLOUT_DEPTH))))
+            }
+            Player::NTuple => {
+                // Checked when the options were parsed
+                let path = self.weights.as_ref().unwrap();
+                let network = NTupleNetwork::from_bytes(&fs::read(path)?)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Couldn't load {path}: {e}")))?;
+                Some(Box::new(NTuplePlayer::new(network)))
+            }
+        })
+    }
+
+    pub(crate) fn from_args(mut args: impl Iterator<Item = String>) -> io::Result<Self> {
+        let mut options = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.as_ref() {
+                "--seed" => {
+                    let value = args.next().ok_or_else(|| invalid_input("--seed requires a value".to_string()))?;
+                    let seed = value.parse().map_err(|_| invalid_input(format!("Invalid seed: {value}")))?;
+                    options.seed = Some(seed);
+                }
+                "--rules" => {
+                    let value = args.next().ok_or_else(|| invalid_input("--rules requires a value".to_string()))?;
+                    options.rules = Rules::try_from(value.as_ref()).map_err(|_| invalid_input(format!("Unknown rules: {value}")))?;
+                }
+                "--size" => {
+                    let value = args.next().ok_or_else(|| invalid_input("--size requires a value".to_string()))?;
+                    options.size = Some(parse_size(&value).ok_or_else(|| invalid_input(format!("Invalid size: {value}")))?);
+                }
+                "--record" => {
+                    options.record = Some(args.next().ok_or_else(|| invalid_input("--record requires a file".to_string()))?);
+                }
+                "--replay" => {
+                    options.replay = Some(args.next().ok_or_else(|| invalid_input("--replay requires a file".to_string()))?);
+                }
+                "--claim" => {
+                    let value = args.next().ok_or_else(|| invalid_input("--claim requires a value".to_string()))?;
+                    options.claim = Some(parse_claim(&value).ok_or_else(|| invalid_input(format!("Invalid claim: {value}")))?);
+                }
+                "--player" | "--ai" => {
+                    let value = args.next().ok_or_else(|| invalid_input(format!("{arg} requires a value")))?;
+                    options.player = Some(Player::try_from(value.as_ref()).map_err(|_| invalid_input(format!("Unknown player: {value}")))?);
+                }
+                "--depth" => {
+                    let value = args.next().ok_or_else(|| invalid_input("--depth requires a value".to_string()))?;
+                    let depth = value.parse().ok().filter(|depth| *depth > 0);
+                    options.search_depth = Some(depth.ok_or_else(|| invalid_input(format!("Invalid depth: {value}")))?);
+                }
+                "--think-time" => {
+                    let value = args.next().ok_or_else(|| invalid_input("--think-time requires a value".to_string()))?;
+                    let millis = value.parse().map_err(|_| invalid_input(format!("Invalid think time: {value}")))?;
+                    options.think_time = Some(Duration::from_millis(millis));
+                }
+                "--rollouts" => {
+                    let value = args.next().ok_or_else(|| invalid_input("--rollouts requires a value".to_string()))?;
+                    let rollouts = value.parse().ok().filter(|rollouts| *rollouts > 0);
+                    options.rollouts = Some(rollouts.ok_or_else(|| invalid_input(format!("Invalid rollout count: {value}")))?);
+                }
+                "--weights" => {
+                    options.weights = Some(args.next().ok_or_else(|| invalid_input("--weights requires a file".to_string()))?);
+                }
+                _ => return Err(invalid_input(format!("Unrecognized argument: {arg}"))),
+            }
+        }
+        if options.claim.is_some() && options.replay.is_none() {
+            return Err(invalid_input("--claim needs a replay to check, given with --replay".to_string()));
+        }
+        if options.search_depth.is_some() && !matches!(options.player, Some(Player::Expectimax | Player::MonteCarlo)) {
+            return Err(invalid_input("--depth only applies to --player expectimax or monte-carlo".to_string()));
+        }
+        if options.think_time.is_some() && options.player != Some(Player::Expectimax) {
+            return Err(invalid_input("--think-time only applies to --player expectimax".to_string()));
+        }
+        if options.rollouts.is_some() && options.player != Some(Player::MonteCarlo) {
+            return Err(invalid_input("--rollouts only applies to --player monte-carlo".to_string()));
+        }
+        if options.weights.is_some() && options.player != Some(Player::NTuple) {
+            return Err(invalid_input("--weights only applies to --player ntuple".to_string()));
+        }
+        if options.player == Some(Player::NTuple) && options.weights.is_none() {
+            return Err(invalid_input("--player ntuple needs a weight file, given with --weights".to_string()));
+        }
+        if let Some((width, height)) = options.size {
+            options.rules = options.rules.with_size(width, height);
+        }
+        Ok(options)
+    }
+}
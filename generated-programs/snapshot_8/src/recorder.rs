@@ -0,0 +1,85 @@
+use std::fs;
+use std::io;
+use std::time::Instant;
+use engine::{Board, Direction, Replay, ReplayMove};
+
+/// Replays written to files with this extension use the compact binary encoding, rather than JSON
+const PACKED_REPLAY_EXTENSION: &str = ".bin";
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+pub(crate) fn read_replay(path: &str) -> io::Result<Replay> {
+    let replay = if path.ends_with(PACKED_REPLAY_EXTENSION) {
+        Replay::from_bytes(&fs::read(path)?)
+    } else {
+        Replay::from_json(&fs::read_to_string(path)?)
+    };
+    replay.map_err(|e| invalid_data(e.to_string()))
+}
+
+pub(crate) fn write_replay(path: &str, replay: &Replay) -> io::Result<()> {
+    if path.ends_with(PACKED_REPLAY_EXTENSION) {
+        fs::write(path, replay.to_bytes())
+    } else {
+        fs::write(path, replay.to_json())
+    }
+}
+
+/// Keeps track of the moves made in the current game, so that they can be written out as a replay
+pub(crate) struct Recorder {
+    /// Cleared if we can no longer vouch for how the game started, e.g. after loading a save
+    replay: Option<Replay>,
+    /// Moves taken back with 'u', most recent last, so that 'r' can put them back
+    undone_moves: Vec<ReplayMove>,
+    started_at: Instant,
+}
+
+impl Recorder {
+    /// Starts recording the game on this board, which must have only just begun
+    pub(crate) fn new(board: &Board) -> Self {
+        Self {
+            replay: Some(Replay::for_board(board)),
+            undone_moves: vec![],
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Stops recording, for when the board no longer follows on from its seed
+    pub(crate) fn abandon(&mut self) {
+        self.replay = None;
+        self.undone_moves.clear();
+    }
+
+    pub(crate) fn record(&mut self, direction: Direction) {
+        let Some(replay) = self.replay.as_mut() else {
+            return;
+        };
+        // Making a fresh move abandons whatever was previously undone, just as it does for the history
+        self.undone_moves.clear();
+        replay.record(direction, Some(self.started_at.elapsed().as_millis() as u64));
+    }
+
+    pub(crate) fn undo(&mut self) {
+        if let Some(undone_move) = self.replay.as_mut().and_then(|replay| replay.moves.pop()) {
+            self.undone_moves.push(undone_move);
+        }
+    }
+
+    pub(crate) fn redo(&mut self) {
+        if let (Some(replay), Some(redone_move)) = (self.replay.as_mut(), self.undone_moves.pop()) {
+            replay.moves.push(redone_move);
+        }
+    }
+
+    /// Writes out every move made so far, along with where the game has got to
+    pub(crate) fn write(&self, path: &str, board: &Board) -> io::Result<()> {
+        let Some(replay) = self.replay.as_ref() else {
+            return Err(io::Error::other("this game was loaded from a save, so its moves weren't recorded"));
+        };
+        let mut replay = replay.clone();
+        replay.record_outcome(board);
+        write_replay(path, &replay)
+    }
+}
@@ -0,0 +1,43 @@
+use wasm_bindgen::prelude::*;
+use engine::{NTupleNetwork, NTuplePlayer, Strategy};
+
+use crate::game::{Game, MoveResult};
+
+/// Plays a `Game` by itself, using an n-tuple network trained by the native `train` binary
+#[wasm_bindgen]
+pub struct Agent {
+    player: NTuplePlayer,
+}
+
+#[wasm_bindgen]
+impl Agent {
+    /// Takes the contents of a weight file, which the page can fetch as an `ArrayBuffer` and pass in as a
+    /// `Uint8Array`
+    #[wasm_bindgen(constructor)]
+    pub fn new(weights: &[u8]) -> Result<Agent, String> {
+        let network = NTupleNetwork::from_bytes(weights).map_err(|e| e.to_string())?;
+        Ok(Self { player: NTuplePlayer::new(network) })
+    }
+
+    /// How many games of self-play went into the weights
+    pub fn games_trained(&self) -> u64 {
+        self.player.network.games_trained
+    }
+
+    /// The move the agent would make next, as `Left`, `Right`, `Up` or `Down`, or nothing once the game is over
+    pub fn suggest(&mut self, game: &Game) -> Option<String> {
+        if game.is_game_over() {
+            return None;
+        }
+        Some(format!("{:?}", self.player.choose(game.board())))
+    }
+
+    /// Makes the agent's next move in the game, or does nothing once the game is over
+    pub fn play(&mut self, game: &mut Game) -> Option<MoveResult> {
+        if game.is_game_over() {
+            return None;
+        }
+        let direction = self.player.choose(game.board());
+        Some(game.play_move(direction))
+    }
+}
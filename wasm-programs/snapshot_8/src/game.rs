@@ -0,0 +1,206 @@
+use wasm_bindgen::prelude::*;
+use engine::{Board, CellContents, Direction, History, MoveOutcome};
+
+/// A summary of a single press, for the page to react to without parsing any text
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub struct MoveResult {
+    /// Whether the press changed the board. If it didn't, no tile was spawned either.
+    pub moved: bool,
+    pub score_delta: usize,
+    pub slides: usize,
+    pub merges: usize,
+    /// Set when this move created the goal tile for the first time in the current game
+    pub reached_goal: bool,
+    /// Set when there's nothing left to do after this move. The board is left as it is, so that the page can show
+    /// it, until `new_game` is called.
+    pub game_over: bool,
+}
+
+/// A game of 2048 that the page can drive directly and draw however it likes. The text-based `game_start` and
+/// `handle_input` are built on top of this.
+#[wasm_bindgen]
+pub struct Game {
+    board: Board,
+    history: History,
+    last_outcome: Option<MoveOutcome>,
+}
+
+impl Game {
+    pub(crate) fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Makes a move, recording it so it can be undone and spawning the next tiles if anything moved
+    pub(crate) fn play(&mut self, direction: Direction) -> MoveOutcome {
+        let board_before_move = self.board.clone();
+        let outcome = self.board.press(direction);
+        if outcome.board_changed() {
+            self.history.record(board_before_move);
+            self.board.spawn_tiles_for_turn();
+        }
+        self.last_outcome = Some(outcome.clone());
+        outcome
+    }
+
+    /// Makes a move, and sums up what happened for the page
+    pub(crate) fn play_move(&mut self, direction: Direction) -> MoveResult {
+        let outcome = self.play(direction);
+        MoveResult {
+            moved: outcome.board_changed(),
+            score_delta: outcome.score_delta,
+            slides: outcome.slides.len(),
+            merges: outcome.merges.len(),
+            reached_goal: outcome.reached_goal,
+            game_over: self.is_game_over(),
+        }
+    }
+
+    /// Clears the board for a new game of the given size, starting from `seed` if one is given. Otherwise the seed
+    /// is drawn from the last game's generator, as in the native program, so that every game can be reproduced from
+    /// the seed it reports.
+    pub(crate) fn restart(&mut self, seed: Option<u64>, width: usize, height: usize) {
+        let rules = self.board.rules().clone();
+        self.board.reset_with_rules(rules.with_size(width, height));
+        self.history.clear();
+        self.last_outcome = None;
+        self.board.start_new_game(seed);
+    }
+
+    pub(crate) fn undo_move(&mut self) -> bool {
+        self.history.undo(&mut self.board)
+    }
+
+    pub(crate) fn redo_move(&mut self) -> bool {
+        self.history.redo(&mut self.board)
+    }
+
+    pub(crate) fn load(&mut self, board: Board) {
+        self.board = board;
+        // The moves we remember belong to a different game
+        self.history.clear();
+        self.last_outcome = None;
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self {
+            board: Board::new(),
+            history: History::new(),
+            last_outcome: None,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Game {
+    /// Starts a game on a board of the given size (4x4 if not given), from the given seed (a random one if not)
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: Option<u64>, width: Option<usize>, height: Option<usize>) -> Result<Game, String> {
+        let mut game = Self::default();
+        game.new_game(seed, width, height)?;
+        Ok(game)
+    }
+
+    /// Starts over, keeping the size of the board unless a new width and/or height is given
+    pub fn new_game(&mut self, seed: Option<u64>, width: Option<usize>, height: Option<usize>) -> Result<(), String> {
+        let (width, height) = (width.unwrap_or(self.board.width()), height.unwrap_or(self.board.height()));
+        if !self.board.rules().clone().with_size(width, height).is_playable() {
+            return Err(format!("Invalid board size: {width}x{height}"));
+        }
+        self.restart(seed, width, height);
+        Ok(())
+    }
+
+    /// Takes a direction as `Left`, `Right`, `Up` or `Down`
+    pub fn press(&mut self, direction: &str) -> Result<MoveResult, String> {
+        let direction = Direction::try_from(direction).map_err(|_| format!("Unrecognized direction: {direction}"))?;
+        Ok(self.play_move(direction))
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.undo_move()
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.redo_move()
+    }
+
+    pub fn width(&self) -> usize {
+        self.board.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.board.height()
+    }
+
+    /// The value of each cell row by row from the top-left, with 0 for an empty cell. This arrives in JS as a
+    /// `Uint32Array`.
+    pub fn cells(&self) -> Vec<u32> {
+        self.board.cells.iter().map(|cell| match cell.contents {
+            CellContents::Empty => 0,
+            CellContents::Occupied(value) => value as u32,
+        }).collect()
+    }
+
+    pub fn score(&self) -> usize {
+        self.board.score()
+    }
+
+    pub fn best_score(&self) -> usize {
+        self.board.best_score()
+    }
+
+    pub fn move_count(&self) -> usize {
+        self.board.move_count()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.board.seed()
+    }
+
+    pub fn goal_tile(&self) -> usize {
+        self.board.goal_tile()
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        !self.board.has_any_legal_move()
+    }
+
+    /// Whether the goal tile has been created in this game. The player may carry on afterwards.
+    pub fn has_won(&self) -> bool {
+        self.board.reached_goal()
+    }
+
+    /// A description of the last move, such as `Left: 2 slid, 1 merged, +8 points`
+    pub fn last_outcome_text(&self) -> Option<String> {
+        self.last_outcome.as_ref().map(|outcome| outcome.to_string())
+    }
+
+    /// What happened to each tile during the last move, as a JSON array of events (see `engine::TileEvent`)
+    pub fn tile_events(&self) -> String {
+        serde_json::to_string(self.board.tile_events()).unwrap()
+    }
+
+    /// Every tile currently on the board as a JSON array, for redrawing from scratch (e.g. after an undo)
+    pub fn tiles(&self) -> String {
+        serde_json::to_string(&self.board.tiles()).unwrap()
+    }
+
+    /// The board drawn as text, as the native program prints it
+    pub fn render(&self) -> String {
+        self.board.to_string()
+    }
+
+    pub fn export_state(&self) -> String {
+        self.board.to_json()
+    }
+
+    /// Picks up a game previously returned by `export_state`. The game is left untouched if the state is rejected.
+    pub fn import_state(&mut self, json: &str) -> Result<(), String> {
+        let board = Board::from_json(json).map_err(|e| e.to_string())?;
+        self.load(board);
+        Ok(())
+    }
+}
@@ -1,14 +1,19 @@
 use wasm_bindgen::prelude::*;
-mod board;
-mod input;
-use crate::board::{Board, CellContents};
-use crate::input::Direction;
+use engine::{Board, Direction};
 
 use lazy_static::lazy_static; // 1.4.0
 use std::sync::Mutex;
 
+mod agent;
+mod game;
+
+pub use crate::agent::Agent;
+pub use crate::game::{Game, MoveResult};
+
 lazy_static! {
-    static ref BOARD: Mutex<Board> = Mutex::new(Board::new());
+    // The game behind the text-based functions below. Pages that want to draw the board themselves can construct
+    // their own `Game` instead.
+    static ref GAME: Mutex<Game> = Mutex::new(Game::default());
 }
 
 #[wasm_bindgen]
@@ -16,22 +21,21 @@ extern {
     pub fn alert(s: &str);
 }
 
+/// The page calls this without any arguments, in which case the next game's seed is drawn from the last game's
+/// generator, and the board keeps the same size as before. Passing a seed reproduces the same game as `--seed` does
+/// in the native program, and passing a width and/or height resizes the board like `--size` does.
 #[wasm_bindgen]
-pub fn game_start() -> String {
+pub fn game_start(seed: Option<u64>, width: Option<usize>, height: Option<usize>) -> String {
+    let mut game = GAME.lock().unwrap();
+    if let Err(e) = game.new_game(seed, width, height) {
+        return e;
+    }
+    let board = game.board();
     let mut out = String::new();
     out.push_str("Game started!\n");
-
-    let mut board = BOARD.lock().unwrap();
-    // Clear the board
-    for i in 0..16 {
-        board.cells[i].contents = CellContents::Empty;
-    }
-    // Spawn a couple tiles
-    board.spawn_tile_in_random_location();
-    board.spawn_tile_in_random_location();
-
+    out.push_str(&format!("Seed: {}\n", board.seed()));
     // Show the initial state of the board
-    out.push_str(&format!("{board}\n"));
+    out.push_str(&format!("{board}"));
 
     out
 }
@@ -40,20 +44,76 @@ pub fn game_start() -> String {
 pub fn handle_input(direction_str: &str) -> String {
     let direction = Direction::try_from(direction_str).unwrap();
     let mut out = String::new();
-    out.push_str(&format!("Processing {direction:?}\n"));
-    let mut board = BOARD.lock().unwrap();
-    board.press(direction);
-    if board.is_full() {
-        out.push_str(&format!("Game over!\n"));
-        // Reset to an empty board
-        board.empty();
-        board.spawn_tile_in_random_location();
-        board.spawn_tile_in_random_location();
+    let mut game = GAME.lock().unwrap();
+    let outcome = game.play(direction);
+    if !outcome.board_changed() {
+        // Nothing was spawned, otherwise the player could fill up the board just by pressing into a wall
+        out.push_str("Nothing moved");
+        return out;
+    }
+    out.push_str(&format!("{outcome}\n"));
+    if outcome.reached_goal {
+        // Swiping again carries on with this game, and the power button already starts a fresh one
+        out.push_str(&format!("You reached {}!\n", game.goal_tile()));
+        out.push_str("Keep swiping to continue playing, or press the power button to start a new game\n");
     }
-    else {
-        board.spawn_tile_in_random_location();
+    if game.is_game_over() {
+        // Show the final state of the board before starting over
+        out.push_str(&format!("{}\n", game.board()));
+        out.push_str("Game over!\n");
+        let (width, height) = (game.width(), game.height());
+        game.restart(None, width, height);
     }
     // Show the new state of the board
-    out.push_str(&format!("{board}"));
+    out.push_str(&format!("{}", game.board()));
     out
 }
+
+#[wasm_bindgen]
+pub fn undo() -> String {
+    let mut game = GAME.lock().unwrap();
+    if !game.undo_move() {
+        return "Nothing to undo".to_string();
+    }
+    format!("Undo\n{}", game.board())
+}
+
+#[wasm_bindgen]
+pub fn redo() -> String {
+    let mut game = GAME.lock().unwrap();
+    if !game.redo_move() {
+        return "Nothing to redo".to_string();
+    }
+    format!("Redo\n{}", game.board())
+}
+
+/// What happened to each tile during the last input, as a JSON array of events (see `engine::TileEvent`), so that
+/// the page can animate tiles sliding, merging and appearing rather than redrawing the whole board
+#[wasm_bindgen]
+pub fn tile_events() -> String {
+    GAME.lock().unwrap().tile_events()
+}
+
+/// Every tile currently on the board as a JSON array, for redrawing from scratch (e.g. after an undo)
+#[wasm_bindgen]
+pub fn tiles() -> String {
+    GAME.lock().unwrap().tiles()
+}
+
+/// The current game as JSON, for the page to stash away (e.g. in local storage) and hand back to `import_state`
+#[wasm_bindgen]
+pub fn export_state() -> String {
+    GAME.lock().unwrap().export_state()
+}
+
+/// Picks up a game previously returned by `export_state`. The board is left untouched if the state is rejected.
+#[wasm_bindgen]
+pub fn import_state(json: &str) -> String {
+    let loaded_board = match Board::from_json(json) {
+        Ok(board) => board,
+        Err(e) => return format!("Couldn't load the game: {e}"),
+    };
+    let mut game = GAME.lock().unwrap();
+    game.load(loaded_board);
+    format!("Game loaded!\n{}", game.board())
+}